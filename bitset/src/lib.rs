@@ -0,0 +1,413 @@
+//! Sets of small non-negative integers stored as the bits of `u64` words.
+//!
+//! [`BitSet`] holds the integers below `64 * N` in an array of `N` words, so it is `Copy` and
+//! allocation free, like a set of letters. [`BitVec`] keeps its words in a vector and grows to fit
+//! the inserted integers, like the set of vertices visited by a search. Both support the set
+//! operations word by word and `shift_or`, adding every element increased by the shift, which is
+//! the step of bitset dynamic programming:
+//!
+//! ```
+//! use bitset::BitVec;
+//!
+//! // Sums of the subsets of the weights.
+//! let mut sums = BitVec::new(11);
+//! sums.insert(0);
+//! for weight in [2, 3, 7] {
+//!     sums.shift_or(weight);
+//! }
+//! assert_eq!(vec![0, 2, 3, 5, 7, 9, 10], sums.iter().collect::<Vec<_>>());
+//! assert_eq!(vec![1, 4, 6, 8], sums.iter_unset().collect::<Vec<_>>());
+//! ```
+
+use std::fmt::{self, Debug, Formatter};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Set of the integers below `64 * N`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet<const N: usize> {
+    words: [u64; N],
+}
+
+impl<const N: usize> BitSet<N> {
+    /// The number of integers the set can hold, those from `0..CAPACITY`.
+    pub const CAPACITY: usize = N * WORD_BITS;
+
+    pub const fn new() -> Self {
+        Self { words: [0; N] }
+    }
+
+    /// Inserts the integer, returning whether it was absent.
+    pub fn insert(&mut self, index: usize) -> bool {
+        debug_assert!(index < Self::CAPACITY);
+        words::insert(&mut self.words, index)
+    }
+
+    /// Removes the integer, returning whether it was present.
+    pub fn remove(&mut self, index: usize) -> bool {
+        debug_assert!(index < Self::CAPACITY);
+        words::remove(&mut self.words, index)
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        debug_assert!(index < Self::CAPACITY);
+        words::contains(&self.words, index)
+    }
+
+    /// Returns the number of elements.
+    pub fn count(&self) -> usize {
+        words::count(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; N];
+    }
+
+    /// Iterates over the elements in increasing order.
+    pub fn iter(&self) -> Bits<'_> {
+        Bits::new(&self.words, 0, Self::CAPACITY)
+    }
+
+    /// Iterates over the integers below the capacity that are not elements, in increasing order.
+    pub fn iter_unset(&self) -> Bits<'_> {
+        Bits::new(&self.words, u64::MAX, Self::CAPACITY)
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        words::combine(&mut self.words, &other.words, |word, other| word | other);
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        words::combine(&mut self.words, &other.words, |word, other| word & other);
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        words::combine(&mut self.words, &other.words, |word, other| word & !other);
+    }
+
+    /// Adds every element increased by the shift, dropping those reaching the capacity.
+    pub fn shift_or(&mut self, shift: usize) {
+        words::shift_or(&mut self.words, shift);
+    }
+}
+
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Debug for BitSet<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for index in iter {
+            set.insert(index);
+        }
+        set
+    }
+}
+
+/// Set of integers from `0..size`, where the size grows to fit the inserted integers.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    /// Words covering the size, with the bits from the size on unset.
+    words: Vec<u64>,
+    size: usize,
+}
+
+impl BitVec {
+    /// Creates an empty set of integers from `0..size`.
+    pub fn new(size: usize) -> Self {
+        Self {
+            words: vec![0; word_count(size)],
+            size,
+        }
+    }
+
+    /// Returns the number of integers the set covers, those from `0..size`.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Changes the size, removing the elements not covered any more.
+    pub fn resize(&mut self, size: usize) {
+        self.words.resize(word_count(size), 0);
+        self.size = size;
+        self.clear_tail();
+    }
+
+    /// Inserts the integer, growing the set to cover it, returning whether it was absent.
+    pub fn insert(&mut self, index: usize) -> bool {
+        if index >= self.size {
+            self.resize(index + 1);
+        }
+        words::insert(&mut self.words, index)
+    }
+
+    /// Removes the integer, returning whether it was present.
+    pub fn remove(&mut self, index: usize) -> bool {
+        index < self.size && words::remove(&mut self.words, index)
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.size && words::contains(&self.words, index)
+    }
+
+    /// Returns the number of elements.
+    pub fn count(&self) -> usize {
+        words::count(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Removes all elements, keeping the size.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Iterates over the elements in increasing order.
+    pub fn iter(&self) -> Bits<'_> {
+        Bits::new(&self.words, 0, self.size)
+    }
+
+    /// Iterates over the integers below the size that are not elements, in increasing order.
+    pub fn iter_unset(&self) -> Bits<'_> {
+        Bits::new(&self.words, u64::MAX, self.size)
+    }
+
+    /// Adds the elements of the other set, growing to its size.
+    pub fn union_with(&mut self, other: &Self) {
+        if other.size > self.size {
+            self.resize(other.size);
+        }
+        words::combine(&mut self.words, &other.words, |word, other| word | other);
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        let common = self.words.len().min(other.words.len());
+        words::combine(&mut self.words, &other.words, |word, other| word & other);
+        self.words[common..].fill(0);
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        words::combine(&mut self.words, &other.words, |word, other| word & !other);
+    }
+
+    /// Adds every element increased by the shift, dropping those reaching the size.
+    pub fn shift_or(&mut self, shift: usize) {
+        words::shift_or(&mut self.words, shift);
+        self.clear_tail();
+    }
+
+    fn clear_tail(&mut self) {
+        let used_bits = self.size % WORD_BITS;
+        if used_bits != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << used_bits) - 1;
+            }
+        }
+    }
+}
+
+impl Debug for BitVec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<usize> for BitVec {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::default();
+        for index in iter {
+            set.insert(index);
+        }
+        set
+    }
+}
+
+fn word_count(size: usize) -> usize {
+    (size + WORD_BITS - 1) / WORD_BITS
+}
+
+/// Iterator over the set bits of words, or over the unset ones, below a limit.
+pub struct Bits<'a> {
+    words: &'a [u64],
+    /// Mask flipping the words, all ones when iterating over the unset bits.
+    flip: u64,
+    limit: usize,
+    word_index: usize,
+    /// Bits of the current word not yielded yet.
+    current: u64,
+}
+
+impl<'a> Bits<'a> {
+    fn new(words: &'a [u64], flip: u64, limit: usize) -> Self {
+        Self {
+            words,
+            flip,
+            limit,
+            word_index: 0,
+            current: words.first().map_or(0, |&word| word ^ flip),
+        }
+    }
+}
+
+impl Iterator for Bits<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.word_index += 1;
+            self.current = self.words.get(self.word_index)? ^ self.flip;
+        }
+        let index = self.word_index * WORD_BITS + self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        (index < self.limit).then_some(index)
+    }
+}
+
+/// Operations on the words shared by both sets.
+mod words {
+    use super::WORD_BITS;
+
+    pub fn insert(words: &mut [u64], index: usize) -> bool {
+        let mask = 1 << (index % WORD_BITS);
+        let word = &mut words[index / WORD_BITS];
+        let absent = *word & mask == 0;
+        *word |= mask;
+        absent
+    }
+
+    pub fn remove(words: &mut [u64], index: usize) -> bool {
+        let mask = 1 << (index % WORD_BITS);
+        let word = &mut words[index / WORD_BITS];
+        let present = *word & mask != 0;
+        *word &= !mask;
+        present
+    }
+
+    pub fn contains(words: &[u64], index: usize) -> bool {
+        words[index / WORD_BITS] >> (index % WORD_BITS) & 1 == 1
+    }
+
+    pub fn count(words: &[u64]) -> usize {
+        words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Combines the words with those of the other set, treating its missing words as zeros.
+    pub fn combine(words: &mut [u64], other: &[u64], operation: impl Fn(u64, u64) -> u64) {
+        for (word, &other) in words.iter_mut().zip(other) {
+            *word = operation(*word, other);
+        }
+    }
+
+    /// Ors the words with themselves shifted towards the higher bits, going from the highest word
+    /// down, so that every word is read before it is changed.
+    pub fn shift_or(words: &mut [u64], shift: usize) {
+        let (word_shift, bit_shift) = (shift / WORD_BITS, shift % WORD_BITS);
+        for index in (word_shift..words.len()).rev() {
+            let source = index - word_shift;
+            let mut shifted = words[source] << bit_shift;
+            if bit_shift != 0 && source > 0 {
+                shifted |= words[source - 1] >> (WORD_BITS - bit_shift);
+            }
+            words[index] |= shifted;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_set() {
+        let mut set = BitSet::<2>::new();
+        assert!(set.is_empty());
+        assert!(set.insert(0));
+        assert!(set.insert(64));
+        assert!(set.insert(127));
+        assert!(!set.insert(64));
+        assert!(set.contains(127));
+        assert!(!set.contains(63));
+        assert_eq!(3, set.count());
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert_eq!(vec![0, 127], set.iter().collect::<Vec<_>>());
+        assert_eq!(126, set.iter_unset().count());
+        assert_eq!("{0, 127}", format!("{set:?}"));
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let first = [1, 2, 3, 70].into_iter().collect::<BitSet<2>>();
+        let second = [2, 3, 4, 100].into_iter().collect::<BitSet<2>>();
+
+        let mut union = first;
+        union.union_with(&second);
+        assert_eq!(vec![1, 2, 3, 4, 70, 100], union.iter().collect::<Vec<_>>());
+        let mut intersection = first;
+        intersection.intersect_with(&second);
+        assert_eq!(vec![2, 3], intersection.iter().collect::<Vec<_>>());
+        let mut difference = first;
+        difference.difference_with(&second);
+        assert_eq!(vec![1, 70], difference.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shift_or() {
+        let mut set = [0, 1, 63, 100].into_iter().collect::<BitSet<2>>();
+        set.shift_or(64);
+        assert_eq!(
+            vec![0, 1, 63, 64, 65, 100, 127],
+            set.iter().collect::<Vec<_>>()
+        );
+
+        let mut set = [0, 62].into_iter().collect::<BitSet<2>>();
+        set.shift_or(3);
+        assert_eq!(vec![0, 3, 62, 65], set.iter().collect::<Vec<_>>());
+
+        let mut vec = [0, 5].into_iter().collect::<BitVec>();
+        vec.shift_or(2);
+        assert_eq!(vec![0, 2, 5], vec.iter().collect::<Vec<_>>());
+        assert_eq!(6, vec.size());
+    }
+
+    #[test]
+    fn test_bit_vec() {
+        let mut vec = BitVec::new(3);
+        assert_eq!(vec![0, 1, 2], vec.iter_unset().collect::<Vec<_>>());
+        assert!(!vec.contains(200));
+        assert!(!vec.remove(200));
+        assert!(vec.insert(200));
+        assert_eq!(201, vec.size());
+        assert!(vec.contains(200));
+        assert_eq!(200, vec.iter_unset().count());
+
+        let mut other = BitVec::new(1000);
+        other.insert(5);
+        other.insert(999);
+        vec.union_with(&other);
+        assert_eq!(vec![5, 200, 999], vec.iter().collect::<Vec<_>>());
+        vec.intersect_with(&[5, 200].into_iter().collect());
+        assert_eq!(vec![5, 200], vec.iter().collect::<Vec<_>>());
+        vec.resize(100);
+        assert_eq!(vec![5], vec.iter().collect::<Vec<_>>());
+        vec.resize(300);
+        assert_eq!(1, vec.count());
+    }
+}
@@ -0,0 +1,159 @@
+//! Turns a problem crate into a single source file for submission.
+//!
+//! ```text
+//! cargo run -p bundle -- contest_14/problem_c [submission.rs]
+//! ```
+//!
+//! Workspace library crates the solution refers to are inlined as modules, exported macros are
+//! hoisted above the solution, `#[derive(Scan)]` is expanded in place, and tests, feature gated
+//! items and library items the solution does not reach are removed. The result is printed to the
+//! standard output unless an output file is given.
+
+mod prune;
+mod transform;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use syn::{Attribute, File, Item};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+type Error = Box<dyn std::error::Error>;
+
+fn main() -> Result<()> {
+    let mut arguments = env::args().skip(1);
+    let problem = arguments
+        .next()
+        .ok_or("Usage: bundle <problem directory> [output file]")?;
+    let output = arguments.next();
+
+    let workspace = Workspace::discover()?;
+    let bundle = workspace.bundle(&workspace.resolve(&problem))?;
+    match output {
+        Some(output) => fs::write(output, bundle)?,
+        None => print!("{bundle}"),
+    }
+    Ok(())
+}
+
+struct Workspace {
+    root: PathBuf,
+    /// Library crates by name, mapped to their root source file.
+    libraries: BTreeMap<String, PathBuf>,
+}
+
+impl Workspace {
+    fn discover() -> Result<Self> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .ok_or("Missing workspace directory")?
+            .to_owned();
+        let mut libraries = BTreeMap::new();
+        for entry in fs::read_dir(&root)? {
+            let path = entry?.path();
+            let library = path.join("src").join("lib.rs");
+            let manifest = fs::read_to_string(path.join("Cargo.toml")).unwrap_or_default();
+            if library.is_file() && !manifest.contains("proc-macro = true") {
+                let name = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .ok_or("Invalid library directory name")?;
+                libraries.insert(name.to_owned(), library);
+            }
+        }
+        Ok(Workspace { root, libraries })
+    }
+
+    fn resolve(&self, problem: &str) -> PathBuf {
+        let problem = Path::new(problem);
+        if problem.exists() {
+            problem.to_owned()
+        } else {
+            self.root.join(problem)
+        }
+    }
+
+    fn bundle(&self, problem: &Path) -> Result<String> {
+        let source_directory = problem.join("src");
+        let (_, mut solution) = parse(&source_directory.join("main.rs"))?;
+        transform::inline_modules(&mut solution.items, &source_directory)?;
+        transform::strip_disabled(&mut solution.items);
+        if transform::expand_derives(&mut solution.items)? {
+            transform::allow_unused_imports(&mut solution.items, "Scan");
+        }
+
+        let mut pending = transform::referenced_crates(&solution.items, &self.libraries);
+        let mut libraries = Vec::new();
+        let mut macros = Vec::new();
+        let mut sources = Vec::new();
+        while let Some(name) = pending.pop() {
+            if libraries.iter().any(|(library, _)| *library == name) {
+                continue;
+            }
+            let root = &self.libraries[&name];
+            let (source, mut library) = parse(root)?;
+            let directory = root.parent().ok_or("Missing library source directory")?;
+            transform::inline_modules(&mut library.items, directory)?;
+            transform::strip_disabled(&mut library.items);
+            transform::expand_derives(&mut library.items)?;
+            pending.extend(transform::referenced_crates(
+                &library.items,
+                &self.libraries,
+            ));
+            transform::qualify_paths(&mut library.items, &name, &self.libraries);
+            for item in transform::take_exported_macros(&mut library.items) {
+                sources.push(transform::macro_source(&item, &source));
+                macros.push(item);
+            }
+            libraries.push((name, library.items));
+        }
+
+        let macro_names = macros
+            .iter()
+            .filter_map(|item| item.ident.clone())
+            .collect::<Vec<_>>();
+        transform::remove_imports(&mut solution.items, &macro_names);
+        for (_, items) in &mut libraries {
+            transform::remove_imports(items, &macro_names);
+        }
+        prune::prune(&solution.items, &macros, &mut libraries);
+
+        let mut items = solution.items;
+        for (name, library) in libraries {
+            items.push(transform::library_module(&name, library));
+        }
+
+        let problem = problem.strip_prefix(&self.root).unwrap_or(problem);
+        let mut bundle = format!(
+            "// Bundled from {}, edit the workspace sources instead.\n\n",
+            problem.display()
+        );
+        if !solution.attrs.is_empty() {
+            bundle.push_str(&unparse(solution.attrs, Vec::new()));
+            bundle.push('\n');
+        }
+        for source in sources {
+            bundle.push_str(&source);
+            bundle.push_str("\n\n");
+        }
+        bundle.push_str(&unparse(Vec::new(), items));
+        Ok(bundle)
+    }
+}
+
+fn unparse(attrs: Vec<Attribute>, items: Vec<Item>) -> String {
+    prettyplease::unparse(&File {
+        shebang: None,
+        attrs,
+        items,
+    })
+}
+
+fn parse(path: &Path) -> Result<(String, File)> {
+    let source = fs::read_to_string(path)
+        .map_err(|error| format!("Cannot read {}: {error}", path.display()))?;
+    let file = syn::parse_file(&source)
+        .map_err(|error| format!("Cannot parse {}: {error}", path.display()))?;
+    Ok((source, file))
+}
@@ -0,0 +1,198 @@
+//! Removal of library items that the solution cannot reach.
+//!
+//! Reachability is approximated by name: an item is kept once any kept item mentions an
+//! identifier equal to its name, starting from the solution and the hoisted macros. Impls follow
+//! the type they are for, or their trait when the type is foreign. Macro invocations are always
+//! kept, since the impls they generate are not visible before expansion, and so are imports,
+//! since traits imported for their methods are never mentioned by name. The path of an import is
+//! followed once a name it imports is mentioned, reaching the modules behind re-exports. Imports
+//! of items removed from another library, or through a removed module, are dropped afterwards, as
+//! they would no longer resolve.
+
+use std::collections::HashSet;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{Ident, Item, ItemMacro, Type, UseTree};
+
+pub fn prune(solution: &[Item], macros: &[ItemMacro], libraries: &mut [(String, Vec<Item>)]) {
+    let mut mentioned = HashSet::new();
+    for item in solution {
+        collect_idents(item.to_token_stream(), &mut mentioned);
+    }
+    for item in macros {
+        collect_idents(item.to_token_stream(), &mut mentioned);
+    }
+
+    let mut kept = libraries
+        .iter()
+        .map(|(_, items)| vec![false; items.len()])
+        .collect::<Vec<_>>();
+    let mut followed = kept.clone();
+    loop {
+        let mut changed = false;
+        for (((_, items), kept), followed) in libraries.iter().zip(&mut kept).zip(&mut followed) {
+            let names = items.iter().map(item_name).collect::<Vec<_>>();
+            for (index, item) in items.iter().enumerate() {
+                if let Item::Use(import) = item {
+                    if !followed[index] && imports_mentioned(&import.tree, &mentioned) {
+                        followed[index] = true;
+                        changed = true;
+                        collect_idents(import.to_token_stream(), &mut mentioned);
+                    }
+                }
+                if kept[index] || !is_reachable(item, &names, kept, &mentioned) {
+                    continue;
+                }
+                kept[index] = true;
+                changed = true;
+                if !matches!(item, Item::Use(_)) {
+                    collect_idents(item.to_token_stream(), &mut mentioned);
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut removed = HashSet::new();
+    for ((library, items), kept) in libraries.iter_mut().zip(kept) {
+        let mut kept = kept.into_iter();
+        items.retain(|item| {
+            let keep = kept.next().unwrap_or(false);
+            if let (false, Some(name)) = (keep, item_name(item)) {
+                removed.insert((library.clone(), name));
+            }
+            keep
+        });
+    }
+    for (library, items) in libraries.iter_mut() {
+        items.retain_mut(|item| match item {
+            Item::Use(item) => match retain_resolved(item.tree.clone(), &[], library, &removed) {
+                Some(tree) => {
+                    item.tree = tree;
+                    true
+                }
+                None => false,
+            },
+            _ => true,
+        });
+    }
+}
+
+/// Keeps the leaves of a qualified use tree, `crate::library::...`, that do not import an item
+/// removed from that library, and drops imports through a module removed from the library of the
+/// import itself, such as the re-export `sieve::Sieve`. Returns `None` when nothing is left.
+fn retain_resolved(
+    tree: UseTree,
+    path: &[String],
+    library: &str,
+    removed: &HashSet<(String, String)>,
+) -> Option<UseTree> {
+    let is_removed = |name: &Ident| match path {
+        [root, library, ..] if root == "crate" => {
+            removed.contains(&(library.clone(), name.to_string()))
+        }
+        _ => false,
+    };
+    match tree {
+        UseTree::Path(mut tree) => {
+            let module = tree.ident.to_string();
+            if path.is_empty() && removed.contains(&(library.to_string(), module.clone())) {
+                return None;
+            }
+            let mut path = path.to_vec();
+            path.push(module);
+            *tree.tree = retain_resolved(*tree.tree, &path, library, removed)?;
+            Some(UseTree::Path(tree))
+        }
+        UseTree::Name(tree) => (!is_removed(&tree.ident)).then_some(UseTree::Name(tree)),
+        UseTree::Rename(tree) => (!is_removed(&tree.ident)).then_some(UseTree::Rename(tree)),
+        UseTree::Glob(tree) => Some(UseTree::Glob(tree)),
+        UseTree::Group(mut group) => {
+            group.items = group
+                .items
+                .into_iter()
+                .filter_map(|tree| retain_resolved(tree, path, library, removed))
+                .collect();
+            (!group.items.is_empty()).then_some(UseTree::Group(group))
+        }
+    }
+}
+
+/// Returns whether the use tree imports a mentioned name, or everything from a module.
+fn imports_mentioned(tree: &UseTree, mentioned: &HashSet<String>) -> bool {
+    match tree {
+        UseTree::Path(tree) => imports_mentioned(&tree.tree, mentioned),
+        UseTree::Name(tree) => mentioned.contains(&tree.ident.to_string()),
+        UseTree::Rename(tree) => mentioned.contains(&tree.rename.to_string()),
+        UseTree::Glob(_) => true,
+        UseTree::Group(group) => group
+            .items
+            .iter()
+            .any(|tree| imports_mentioned(tree, mentioned)),
+    }
+}
+
+fn is_reachable(
+    item: &Item,
+    names: &[Option<String>],
+    kept: &[bool],
+    mentioned: &HashSet<String>,
+) -> bool {
+    match item {
+        Item::Macro(item) if item.ident.is_none() => true,
+        Item::Use(_) => true,
+        Item::Impl(item) => {
+            let local = type_name(&item.self_ty)
+                .and_then(|name| names.iter().position(|item| item.as_ref() == Some(&name)));
+            match (local, &item.trait_) {
+                (Some(index), _) => kept[index],
+                (None, Some((_, path, _))) => path
+                    .segments
+                    .last()
+                    .map_or(false, |last| mentioned.contains(&last.ident.to_string())),
+                (None, None) => false,
+            }
+        }
+        item => item_name(item).map_or(true, |name| mentioned.contains(&name)),
+    }
+}
+
+fn item_name(item: &Item) -> Option<String> {
+    let ident = match item {
+        Item::Const(item) => &item.ident,
+        Item::Enum(item) => &item.ident,
+        Item::Fn(item) => &item.sig.ident,
+        Item::Macro(item) => item.ident.as_ref()?,
+        Item::Mod(item) => &item.ident,
+        Item::Static(item) => &item.ident,
+        Item::Struct(item) => &item.ident,
+        Item::Trait(item) => &item.ident,
+        Item::Type(item) => &item.ident,
+        Item::Union(item) => &item.ident,
+        _ => return None,
+    };
+    Some(ident.to_string())
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(ty) => ty.path.segments.last().map(|last| last.ident.to_string()),
+        Type::Reference(ty) => type_name(&ty.elem),
+        _ => None,
+    }
+}
+
+fn collect_idents(tokens: TokenStream, idents: &mut HashSet<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                idents.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
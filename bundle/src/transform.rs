@@ -0,0 +1,382 @@
+//! Syntax tree rewrites applied to the solution and the inlined libraries.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{self, PathBuf};
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Brace;
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    parse_quote, Attribute, DeriveInput, File, Ident, ImplItem, Item, ItemMacro, ItemUse, Meta,
+    Path, PathSegment, Token, TraitItem, UseTree,
+};
+
+use crate::Result;
+
+/// Replaces `mod name;` declarations with the items of their files, looked up as Cargo does:
+/// `name.rs` or `name/mod.rs` in the directory of the declaring file, with their own submodules in
+/// `name/`.
+pub fn inline_modules(items: &mut [Item], directory: &path::Path) -> Result<()> {
+    for item in items {
+        let Item::Mod(item) = item else {
+            continue;
+        };
+        let directory = directory.join(item.ident.to_string());
+        if item.content.is_none() {
+            let path = [directory.with_extension("rs"), directory.join("mod.rs")]
+                .into_iter()
+                .find(|path| path.is_file())
+                .ok_or_else(|| format!("Missing the file of module {}", item.ident))?;
+            let file = syn::parse_file(&fs::read_to_string(&path)?)?;
+            item.content = Some((Brace::default(), file.items));
+            item.semi = None;
+        }
+        if let Some((_, items)) = &mut item.content {
+            inline_modules(items, &directory)?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes tests and items behind features, the bundle is always built without either.
+pub fn strip_disabled(items: &mut Vec<Item>) {
+    items.retain(|item| !is_disabled(item_attributes(item)));
+    for item in items {
+        match item {
+            Item::Mod(item) => {
+                if let Some((_, items)) = &mut item.content {
+                    strip_disabled(items);
+                }
+                continue;
+            }
+            Item::Impl(item) => item.items.retain(|item| match item {
+                ImplItem::Const(item) => !is_disabled(&item.attrs),
+                ImplItem::Fn(item) => !is_disabled(&item.attrs),
+                ImplItem::Type(item) => !is_disabled(&item.attrs),
+                _ => true,
+            }),
+            Item::Trait(item) => item.items.retain(|item| match item {
+                TraitItem::Const(item) => !is_disabled(&item.attrs),
+                TraitItem::Fn(item) => !is_disabled(&item.attrs),
+                TraitItem::Type(item) => !is_disabled(&item.attrs),
+                _ => true,
+            }),
+            _ => {}
+        }
+    }
+}
+
+fn is_disabled(attributes: &[Attribute]) -> bool {
+    attributes
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("cfg"))
+        .any(|attribute| match attribute.parse_args::<Meta>() {
+            Ok(Meta::Path(path)) => path.is_ident("test"),
+            Ok(Meta::NameValue(name_value)) => name_value.path.is_ident("feature"),
+            _ => false,
+        })
+}
+
+fn item_attributes(item: &Item) -> &[Attribute] {
+    match item {
+        Item::Const(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::ExternCrate(item) => &item.attrs,
+        Item::Fn(item) => &item.attrs,
+        Item::ForeignMod(item) => &item.attrs,
+        Item::Impl(item) => &item.attrs,
+        Item::Macro(item) => &item.attrs,
+        Item::Mod(item) => &item.attrs,
+        Item::Static(item) => &item.attrs,
+        Item::Struct(item) => &item.attrs,
+        Item::Trait(item) => &item.attrs,
+        Item::TraitAlias(item) => &item.attrs,
+        Item::Type(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        Item::Use(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// Replaces `#[derive(Scan)]` with the implementation it would generate. Returns whether any
+/// derive was expanded.
+pub fn expand_derives(items: &mut Vec<Item>) -> Result<bool> {
+    let mut expanded = false;
+    let mut index = 0;
+    while index < items.len() {
+        match &mut items[index] {
+            Item::Struct(item) if derives(&item.attrs).iter().any(is_scan) => {
+                let input = syn::parse2::<DeriveInput>(item.to_token_stream())?;
+                remove_scan_derive(&mut item.attrs);
+                for field in &mut item.fields {
+                    field
+                        .attrs
+                        .retain(|attribute| !attribute.path().is_ident("scan"));
+                }
+                let expansion = syn::parse2::<File>(scan_codegen::expand(input)?)?;
+                for generated in expansion.items {
+                    index += 1;
+                    items.insert(index, generated);
+                }
+                expanded = true;
+            }
+            Item::Mod(item) => {
+                if let Some((_, items)) = &mut item.content {
+                    expanded |= expand_derives(items)?;
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    Ok(expanded)
+}
+
+/// Allows imports of `name` to go unused. The expanded derives refer to the `Scan` trait by its
+/// full path, so an import that was there for the derive may no longer be needed.
+pub fn allow_unused_imports(items: &mut [Item], name: &str) {
+    fn imports(tree: &UseTree, name: &str) -> bool {
+        match tree {
+            UseTree::Path(tree) => imports(&tree.tree, name),
+            UseTree::Name(tree) => tree.ident == name,
+            UseTree::Rename(tree) => tree.ident == name,
+            UseTree::Glob(_) => false,
+            UseTree::Group(group) => group.items.iter().any(|tree| imports(tree, name)),
+        }
+    }
+
+    for item in items {
+        if let Item::Use(item) = item {
+            if imports(&item.tree, name) {
+                item.attrs.push(parse_quote!(#[allow(unused_imports)]));
+            }
+        }
+    }
+}
+
+fn derives(attributes: &[Attribute]) -> Vec<Path> {
+    attributes
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("derive"))
+        .filter_map(|attribute| {
+            attribute
+                .parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .collect()
+}
+
+fn is_scan(path: &Path) -> bool {
+    path.segments
+        .last()
+        .map_or(false, |last| last.ident == "Scan")
+}
+
+fn remove_scan_derive(attributes: &mut Vec<Attribute>) {
+    let others = derives(attributes)
+        .into_iter()
+        .filter(|path| !is_scan(path))
+        .collect::<Punctuated<Path, Token![,]>>();
+    attributes.retain(|attribute| !attribute.path().is_ident("derive"));
+    if !others.is_empty() {
+        attributes.insert(0, parse_quote!(#[derive(#others)]));
+    }
+}
+
+/// Returns the workspace libraries the items refer to by path.
+pub fn referenced_crates(items: &[Item], libraries: &BTreeMap<String, PathBuf>) -> Vec<String> {
+    struct References<'a> {
+        libraries: &'a BTreeMap<String, PathBuf>,
+        found: Vec<String>,
+    }
+
+    impl References<'_> {
+        fn add(&mut self, ident: &Ident) {
+            let name = ident.to_string();
+            if self.libraries.contains_key(&name) && !self.found.contains(&name) {
+                self.found.push(name);
+            }
+        }
+    }
+
+    impl<'ast> Visit<'ast> for References<'_> {
+        fn visit_item_use(&mut self, item: &'ast ItemUse) {
+            if item.leading_colon.is_none() {
+                match &item.tree {
+                    UseTree::Path(tree) => self.add(&tree.ident),
+                    UseTree::Name(tree) => self.add(&tree.ident),
+                    _ => {}
+                }
+            }
+        }
+
+        fn visit_path(&mut self, path: &'ast Path) {
+            if path.leading_colon.is_none() && path.segments.len() > 1 {
+                self.add(&path.segments[0].ident);
+            }
+            visit::visit_path(self, path);
+        }
+    }
+
+    let mut references = References {
+        libraries,
+        found: Vec::new(),
+    };
+    for item in items {
+        references.visit_item(item);
+    }
+    references.found
+}
+
+/// Rewrites paths of a library about to become the module `name`, so that they resolve from
+/// inside it: `crate::` paths point into the module and other libraries are reached through the
+/// crate root.
+pub fn qualify_paths(items: &mut [Item], name: &str, libraries: &BTreeMap<String, PathBuf>) {
+    struct Qualify<'a> {
+        name: Ident,
+        libraries: &'a BTreeMap<String, PathBuf>,
+    }
+
+    impl VisitMut for Qualify<'_> {
+        fn visit_item_use_mut(&mut self, item: &mut ItemUse) {
+            if item.leading_colon.is_some() {
+                return;
+            }
+            let UseTree::Path(tree) = &mut item.tree else {
+                return;
+            };
+            if tree.ident == "crate" {
+                let name = &self.name;
+                let rest = &tree.tree;
+                *tree.tree = parse_quote!(#name::#rest);
+            } else if self.libraries.contains_key(&tree.ident.to_string()) {
+                let old = item.tree.clone();
+                item.tree = parse_quote!(crate::#old);
+            }
+        }
+
+        fn visit_path_mut(&mut self, path: &mut Path) {
+            if path.leading_colon.is_none() && path.segments.len() > 1 {
+                let first = &path.segments[0].ident;
+                if first == "crate" {
+                    path.segments
+                        .insert(1, PathSegment::from(self.name.clone()));
+                } else if self.libraries.contains_key(&first.to_string()) {
+                    let root = Ident::new("crate", Span::call_site());
+                    path.segments.insert(0, PathSegment::from(root));
+                }
+            }
+            visit_mut::visit_path_mut(self, path);
+        }
+    }
+
+    let mut qualify = Qualify {
+        name: Ident::new(name, Span::call_site()),
+        libraries,
+    };
+    for item in items {
+        qualify.visit_item_mut(item);
+    }
+}
+
+/// Removes `#[macro_export]` macros from a library, they are placed at the top of the bundle.
+pub fn take_exported_macros(items: &mut Vec<Item>) -> Vec<ItemMacro> {
+    let mut macros = Vec::new();
+    let mut index = 0;
+    while index < items.len() {
+        match &items[index] {
+            Item::Macro(item) if item.attrs.iter().any(is_macro_export) => {
+                let Item::Macro(mut item) = items.remove(index) else {
+                    unreachable!();
+                };
+                item.attrs.retain(|attribute| !is_macro_export(attribute));
+                macros.push(item);
+            }
+            _ => index += 1,
+        }
+    }
+    macros
+}
+
+fn is_macro_export(attribute: &Attribute) -> bool {
+    attribute.path().is_ident("macro_export")
+}
+
+/// Returns the source text of a hoisted macro, which printing the syntax tree would reflow.
+pub fn macro_source(item: &ItemMacro, source: &str) -> String {
+    let mut text = String::new();
+    for attribute in &item.attrs {
+        text.push_str(&source[attribute.span().byte_range()]);
+        text.push('\n');
+    }
+    let start = item.mac.path.span().byte_range().start;
+    let end = item.span().byte_range().end;
+    text.push_str(&source[start..end]);
+    text
+}
+
+/// Removes imports of the given names, which are hoisted macros visible without importing.
+pub fn remove_imports(items: &mut Vec<Item>, names: &[Ident]) {
+    items.retain_mut(|item| match item {
+        Item::Use(item) => {
+            match retain_imports(item.tree.clone(), None, &|name| !names.contains(name)) {
+                Some(tree) => {
+                    item.tree = tree;
+                    true
+                }
+                None => false,
+            }
+        }
+        _ => true,
+    });
+}
+
+/// Keeps the leaves of a use tree whose imported name passes the filter, `self` imports being
+/// named after their parent. Returns `None` when nothing is left.
+fn retain_imports(
+    tree: UseTree,
+    parent: Option<&Ident>,
+    keep: &impl Fn(&Ident) -> bool,
+) -> Option<UseTree> {
+    match tree {
+        UseTree::Path(mut path) => {
+            *path.tree = retain_imports(*path.tree, Some(&path.ident), keep)?;
+            Some(UseTree::Path(path))
+        }
+        UseTree::Name(name) => {
+            let imported = if name.ident == "self" {
+                parent
+            } else {
+                Some(&name.ident)
+            };
+            imported.map_or(true, keep).then_some(UseTree::Name(name))
+        }
+        UseTree::Rename(rename) => keep(&rename.rename).then_some(UseTree::Rename(rename)),
+        UseTree::Glob(glob) => Some(UseTree::Glob(glob)),
+        UseTree::Group(mut group) => {
+            group.items = group
+                .items
+                .into_iter()
+                .filter_map(|tree| retain_imports(tree, parent, keep))
+                .collect();
+            (!group.items.is_empty()).then_some(UseTree::Group(group))
+        }
+    }
+}
+
+/// Wraps the items of a library into a module of its name.
+pub fn library_module(name: &str, items: Vec<Item>) -> Item {
+    let name = Ident::new(name, Span::call_site());
+    parse_quote! {
+        #[allow(dead_code, unused_imports)]
+        mod #name {
+            #(#items)*
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+type Error = Box<dyn std::error::Error>;
+
+static COMPILATIONS: AtomicUsize = AtomicUsize::new(0);
+
+fn workspace() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("bundle is a workspace member")
+        .to_owned()
+}
+
+/// Bundles the problem and compiles the bundle on its own, returning the executable.
+fn compile(problem: &str) -> Result<PathBuf> {
+    let output = Command::new(env!("CARGO_BIN_EXE_bundle"))
+        .arg(problem)
+        .output()?;
+    assert!(
+        output.status.success(),
+        "bundling {problem} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let source = String::from_utf8(output.stdout)?;
+    assert!(!source.contains("#[cfg(test)]"));
+
+    let directory = env::temp_dir().join("bundle-tests");
+    fs::create_dir_all(&directory)?;
+    // Tests run in parallel and may compile the same problem.
+    let name = format!(
+        "{}_{}",
+        problem.replace('/', "_"),
+        COMPILATIONS.fetch_add(1, Ordering::Relaxed)
+    );
+    let source_path = directory.join(format!("{name}.rs"));
+    let executable = directory.join(name);
+    fs::write(&source_path, source)?;
+
+    let output = rustc()
+        .args(["--edition", "2021", "-D", "warnings", "-o"])
+        .arg(&executable)
+        .arg(&source_path)
+        .output()?;
+    assert!(
+        output.status.success(),
+        "compiling the bundle of {problem} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(executable)
+}
+
+/// Returns the compiler for the bundles: the one in `RUSTC` if set, otherwise the toolchain of the
+/// workspace's minimum supported Rust version, which the judges may run, when rustup has it
+/// installed, and the default one with a message when not.
+fn rustc() -> Command {
+    if let Ok(rustc) = env::var("RUSTC") {
+        return Command::new(rustc);
+    }
+    let toolchain = format!("+{}", env!("CARGO_PKG_RUST_VERSION"));
+    let installed = Command::new("rustc")
+        .args([&toolchain, "--version"])
+        .output()
+        .map_or(false, |output| output.status.success());
+    let mut rustc = Command::new("rustc");
+    if installed {
+        rustc.arg(toolchain);
+    } else {
+        eprintln!(
+            "the {toolchain} toolchain is not installed, so the bundles are compiled with the \
+             default one and their compatibility with it is not checked"
+        );
+    }
+    rustc
+}
+
+fn run(executable: &Path, input: &str) -> Result<String> {
+    let mut child = Command::new(executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .ok_or("Missing standard input")?
+        .write_all(input.as_bytes())?;
+    let output = child.wait_with_output()?;
+    assert!(output.status.success());
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[test]
+fn test_every_problem_compiles() -> Result<()> {
+    let mut problems = Vec::new();
+    for contest in fs::read_dir(workspace())? {
+        let contest = contest?.path();
+        let Some(contest_name) = contest.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !contest_name.starts_with("contest_") {
+            continue;
+        }
+        for problem in fs::read_dir(&contest)? {
+            let problem = problem?.path();
+            if problem.join("src").join("main.rs").is_file() {
+                let problem_name = problem.file_name().ok_or("Missing problem name")?;
+                problems.push(format!("{contest_name}/{}", problem_name.to_string_lossy()));
+            }
+        }
+    }
+    assert!(!problems.is_empty());
+    for problem in problems {
+        compile(&problem)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_scanner_with_derive() -> Result<()> {
+    let executable = compile("contest_4/problem_a")?;
+    let output = run(&executable, "5 5\n1 2 1 2 1\n3 10 1 1 1\n")?;
+    assert_eq!("3\n5\n4\n4\n3\n", output);
+    Ok(())
+}
+
+#[test]
+fn test_hoisted_macro() -> Result<()> {
+    let executable = compile("contest_14/problem_a")?;
+    let output = run(&executable, "2\n0 0\n1 1\n")?;
+    assert_eq!("1\n", output);
+    Ok(())
+}
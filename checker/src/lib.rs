@@ -0,0 +1,249 @@
+//! Checkers deciding whether a program output is a correct answer.
+//!
+//! Most problems have a single correct answer, compared with the expected output token by token by
+//! [`Exact`]. Others are judged more leniently: [`Floats`] accepts floats within a tolerance and
+//! [`YesNo`] accepts `YES` and `NO` in any case. Problems accepting several correct answers
+//! implement [`Checker`] themselves, usually as a function validating the answer against the
+//! input, e.g. that a printed permutation has the required properties.
+//!
+//! ```
+//! use checker::{Checker, Exact, Floats};
+//!
+//! assert!(Exact.check("", "1 2\n", "1\n2").is_ok());
+//! assert!(Floats::new(1e-6).check("", "0.5\n", "0.5000001").is_ok());
+//! assert_eq!(
+//!     Err("token 1: expected `1`, found `2`".to_owned()),
+//!     Exact.check("", "1", "2")
+//! );
+//! ```
+
+use std::fmt::Display;
+use std::str::{FromStr, SplitWhitespace};
+
+/// Decides whether the actual output is a correct answer to the input, given the expected output.
+/// Returns a description of the first problem found otherwise.
+pub trait Checker {
+    fn check(&self, input: &str, expected: &str, actual: &str) -> Result<(), String>;
+}
+
+impl<F: Fn(&str, &str, &str) -> Result<(), String>> Checker for F {
+    fn check(&self, input: &str, expected: &str, actual: &str) -> Result<(), String> {
+        self(input, expected, actual)
+    }
+}
+
+/// Accepts output consisting of the expected tokens, however separated by whitespace.
+#[derive(Copy, Clone, Debug)]
+pub struct Exact;
+
+impl Checker for Exact {
+    fn check(&self, _input: &str, expected: &str, actual: &str) -> Result<(), String> {
+        compare_tokens(expected, actual, |expected, actual| expected == actual)
+    }
+}
+
+/// Accepts `YES` and `NO` tokens in any case, other tokens have to be exact.
+#[derive(Copy, Clone, Debug)]
+pub struct YesNo;
+
+impl Checker for YesNo {
+    fn check(&self, _input: &str, expected: &str, actual: &str) -> Result<(), String> {
+        compare_tokens(expected, actual, |expected, actual| {
+            let is_verdict = ["YES", "NO"]
+                .iter()
+                .any(|verdict| expected.eq_ignore_ascii_case(verdict));
+            if is_verdict {
+                expected.eq_ignore_ascii_case(actual)
+            } else {
+                expected == actual
+            }
+        })
+    }
+}
+
+/// Accepts floats whose absolute or relative error does not exceed the bounds, other tokens have
+/// to be exact.
+#[derive(Copy, Clone, Debug)]
+pub struct Floats {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Floats {
+    /// Uses the same bound for the absolute and the relative error, as problems usually do.
+    pub fn new(epsilon: f64) -> Self {
+        Self {
+            absolute: epsilon,
+            relative: epsilon,
+        }
+    }
+
+    fn accepts(&self, expected: f64, actual: f64) -> bool {
+        let error = (expected - actual).abs();
+        error <= self.absolute || error <= self.relative * expected.abs()
+    }
+}
+
+impl Checker for Floats {
+    fn check(&self, _input: &str, expected: &str, actual: &str) -> Result<(), String> {
+        compare_tokens(expected, actual, |expected, actual| {
+            match (expected.parse::<f64>(), actual.parse::<f64>()) {
+                (Ok(expected), Ok(actual)) => self.accepts(expected, actual),
+                _ => expected == actual,
+            }
+        })
+    }
+}
+
+/// Compares the outputs token by token with the given equality, describing the first mismatch.
+fn compare_tokens(
+    expected: &str,
+    actual: &str,
+    equal: impl Fn(&str, &str) -> bool,
+) -> Result<(), String> {
+    let mut expected_tokens = expected.split_whitespace();
+    let mut actual_tokens = actual.split_whitespace();
+    let mut index = 1;
+    loop {
+        let mismatch = match (expected_tokens.next(), actual_tokens.next()) {
+            (None, None) => return Ok(()),
+            (Some(expected), Some(actual)) if equal(expected, actual) => {
+                index += 1;
+                continue;
+            }
+            (Some(expected), Some(actual)) => format!("expected `{expected}`, found `{actual}`"),
+            (Some(expected), None) => format!("expected `{expected}`, found the end of output"),
+            (None, Some(actual)) => format!("expected the end of output, found `{actual}`"),
+        };
+        return Err(format!("token {index}: {mismatch}"));
+    }
+}
+
+/// Whitespace separated tokens of an input or output, read by custom checkers. Errors name the
+/// position of the offending token.
+pub struct Tokens<'a> {
+    tokens: SplitWhitespace<'a>,
+    position: usize,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            tokens: text.split_whitespace(),
+            position: 0,
+        }
+    }
+
+    pub fn next_token(&mut self) -> Result<&'a str, String> {
+        self.position += 1;
+        let position = self.position;
+        self.tokens
+            .next()
+            .ok_or_else(|| format!("token {position}: unexpected end of output"))
+    }
+
+    pub fn parse<T: FromStr>(&mut self) -> Result<T, String> {
+        let token = self.next_token()?;
+        token.parse().map_err(|_| {
+            let type_name = std::any::type_name::<T>();
+            self.error(format!("cannot parse `{token}` as {type_name}"))
+        })
+    }
+
+    /// Checks that no tokens are left.
+    pub fn finish(mut self) -> Result<(), String> {
+        match self.tokens.next() {
+            None => Ok(()),
+            Some(token) => Err(format!(
+                "token {}: unexpected trailing `{token}`",
+                self.position + 1
+            )),
+        }
+    }
+
+    /// Describes a problem with the last read token.
+    pub fn error(&self, message: impl Display) -> String {
+        format!("token {}: {message}", self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact() {
+        assert_eq!(Ok(()), Exact.check("", "1 2\n3\n", "1  2 3"));
+        assert_eq!(Ok(()), Exact.check("", "", "\n"));
+        assert_eq!(
+            Err("token 3: expected `3`, found `4`".to_owned()),
+            Exact.check("", "1 2\n3\n", "1 2\n4\n")
+        );
+        assert_eq!(
+            Err("token 2: expected `NO`, found the end of output".to_owned()),
+            Exact.check("", "YES\nNO\n", "YES\n")
+        );
+        assert_eq!(
+            Err("token 2: expected the end of output, found `2`".to_owned()),
+            Exact.check("", "1\n", "1 2\n")
+        );
+        assert!(Exact.check("", "YES", "yes").is_err());
+    }
+
+    #[test]
+    fn test_yes_no() {
+        assert_eq!(Ok(()), YesNo.check("", "YES\nNO\n", "yes\nNo\n"));
+        assert_eq!(Ok(()), YesNo.check("", "YES\nabc\n", "yEs\nabc\n"));
+        assert!(YesNo.check("", "YES", "NO").is_err());
+        assert!(YesNo.check("", "YES\nabc\n", "YES\nABC\n").is_err());
+    }
+
+    #[test]
+    fn test_floats() {
+        let checker = Floats::new(1e-6);
+        assert_eq!(
+            Ok(()),
+            checker.check("", "YES\n25.5 4.5", "YES\n25.5000004 4.499999")
+        );
+        assert_eq!(Ok(()), checker.check("", "1000000", "1000000.5"));
+        assert_eq!(Ok(()), checker.check("", "0.0000000000", "0"));
+        assert_eq!(
+            Err("token 1: expected `0.5`, found `0.50001`".to_owned()),
+            checker.check("", "0.5", "0.50001")
+        );
+        assert!(checker.check("", "YES 1", "NO 1").is_err());
+        assert!(checker.check("", "1", "NaN").is_err());
+    }
+
+    #[test]
+    fn test_custom() {
+        fn sums_to_input(input: &str, _expected: &str, actual: &str) -> Result<(), String> {
+            let mut input = Tokens::new(input);
+            let sum = input.parse::<u32>()?;
+            let mut actual = Tokens::new(actual);
+            let (first, second) = (actual.parse::<u32>()?, actual.parse::<u32>()?);
+            if first + second != sum {
+                return Err(actual.error(format!("{first} + {second} is not {sum}")));
+            }
+            actual.finish()
+        }
+
+        assert_eq!(Ok(()), sums_to_input.check("5", "2 3", "4 1"));
+        assert_eq!(
+            Err("token 2: 4 + 2 is not 5".to_owned()),
+            sums_to_input.check("5", "2 3", "4 2")
+        );
+        assert_eq!(
+            Err("token 2: unexpected end of output".to_owned()),
+            sums_to_input.check("5", "2 3", "4")
+        );
+        assert_eq!(
+            Err("token 1: cannot parse `x` as u32".to_owned()),
+            sums_to_input.check("5", "2 3", "x 1")
+        );
+        assert_eq!(
+            Err("token 3: unexpected trailing `0`".to_owned()),
+            sums_to_input.check("5", "2 3", "4 1 0")
+        );
+    }
+}
@@ -1,33 +1,28 @@
-use std::io;
-use std::io::BufRead;
+use problem::Problem;
+use scanner::Scan;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error + 'static>;
 
 fn main() -> Result<()> {
-    let integers = get_input()?;
-    let sum = minimum_possible_sum(integers)?;
-    println!("{sum}");
-    Ok(())
+    problem::run::<Integers>()
 }
 
-fn get_input() -> Result<Vec<u32>> {
-    let stdin = io::stdin().lock();
-
-    let lines = stdin.lines().collect::<Result<Vec<_>, _>>()?;
-    assert_eq!(lines.len(), 2);
-
-    let integers_count_line = lines.get(0).ok_or("Missing first line")?;
-    let integers_line = lines.get(1).ok_or("Missing second line")?;
+#[derive(Scan)]
+struct Integers {
+    #[scan(range = 1..=2000)]
+    integers_count: usize,
+    #[scan(len = integers_count, range = 0..=10u32.pow(6))]
+    integers: Vec<u32>,
+}
 
-    let integers_count = integers_count_line.parse::<usize>()?;
-    let integers = integers_line
-        .split_whitespace()
-        .map(|integer| integer.parse::<u32>())
-        .collect::<Result<Vec<_>, _>>()?;
-    assert_eq!(integers.len(), integers_count);
+impl Problem for Integers {
+    type Input = Self;
+    type Output = u32;
 
-    Ok(integers)
+    fn solve(input: Self) -> Result<u32> {
+        minimum_possible_sum(input.integers)
+    }
 }
 
 fn minimum_possible_sum(integers: Vec<u32>) -> Result<u32> {
@@ -1,23 +1,14 @@
 use std::array::TryFromSliceError;
 use std::cmp::Reverse;
-use std::io;
-use std::io::BufRead;
+
+use problem::Problem;
+use scanner::{LineScanner, Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error + 'static>;
 
-trait Problem<T>: Sized {
-    fn from_reader(reader: impl BufRead) -> Result<Self>;
-
-    fn solve(self) -> Result<T>;
-}
-
 fn main() -> Result<()> {
-    let stdin = io::stdin().lock();
-    let problem = Tape::from_reader(stdin)?;
-    let result = problem.solve()?;
-    println!("{result}");
-    Ok(())
+    problem::run::<Tape>()
 }
 
 struct Tape {
@@ -30,43 +21,37 @@ struct Stick {
     broken_segments: Vec<u32>,
 }
 
-impl Problem<u32> for Tape {
-    fn from_reader(reader: impl BufRead) -> Result<Self> {
-        fn split_line(line: &str) -> Result<Vec<u32>> {
-            let result = line
-                .split_whitespace()
-                .map(|integer| integer.parse::<u32>())
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(result)
-        }
-
-        let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
-        let first_line = lines.get(0).ok_or("Missing first line")?;
-        let second_line = lines.get(1).ok_or("Missing second line")?;
-
-        let first_line = split_line(first_line)?;
-        let broken_segment_count = *first_line.first().ok_or("Missing broken segment count")?;
-        let segment_count = *first_line.get(1).ok_or("Missing segment count")?;
-        let tape_pieces = *first_line.get(2).ok_or("Missing tape pieces")?;
-        let broken_segments = split_line(second_line)?;
-        assert_eq!(broken_segments.len(), broken_segment_count as usize);
+impl Scan for Tape {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        let (broken_segment_count, segment_count, tape_pieces) = {
+            let mut line = scanner.next_line()?;
+            let broken_segment_count = line.next_ranged(1..=100_000)?;
+            let segment_count = line.next_ranged(broken_segment_count..=1_000_000_000)?;
+            let tape_pieces = line.next_ranged(1..=broken_segment_count)?;
+            (broken_segment_count, segment_count, tape_pieces)
+        };
+        let broken_segments = scanner
+            .next_line()?
+            .next_vec(broken_segment_count as usize, 1..=segment_count)?;
 
         let stick = Stick {
             segment_count,
             broken_segments,
         };
-        let tape = Tape { stick, tape_pieces };
+        Ok(Tape { stick, tape_pieces })
+    }
+}
 
-        assert!((1..=100_000).contains(&broken_segment_count));
-        assert!((broken_segment_count..=1_000_000_000).contains(&tape.stick.segment_count));
-        assert!((1..=broken_segment_count).contains(&tape.tape_pieces));
-        for segment in &tape.stick.broken_segments {
-            assert!((1..=tape.stick.segment_count).contains(segment))
-        }
+impl Problem for Tape {
+    type Input = Self;
+    type Output = u32;
 
-        Ok(tape)
+    fn solve(input: Self) -> Result<u32> {
+        input.solve()
     }
+}
 
+impl Tape {
     /// Returns the minimum total length of the tape pieces.
     ///
     /// Idea:
@@ -78,6 +63,7 @@ impl Problem<u32> for Tape {
         let broken_segments = &self.stick.broken_segments;
         let first_broken_segment = broken_segments.first().ok_or("broken_segments are empty")?;
         let last_broken_segment = broken_segments.last().ok_or("broken_segments are empty")?;
+        debug_assert!(*last_broken_segment <= self.stick.segment_count);
         let tape_length = last_broken_segment - first_broken_segment + 1;
 
         let mut broken_segments_distances = broken_segments
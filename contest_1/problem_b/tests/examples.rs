@@ -0,0 +1 @@
+harness::test_examples!();
@@ -1,23 +1,14 @@
-use std::io::BufRead;
-use std::ops::Range;
-use std::{cmp, io};
+use std::cmp;
+
+use output::Verdict;
+use problem::Problem;
+use scanner::{LineScanner, Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error + 'static>;
 
-trait Problem<T>: Sized {
-    fn from_reader(reader: impl BufRead) -> Result<Self>;
-
-    fn solve(self) -> Result<T>;
-}
-
 fn main() -> Result<()> {
-    let stdin = io::stdin().lock();
-    let problem = TransposingMatrices::from_reader(stdin)?;
-    let result = problem.solve()?;
-    let result = if result { "YES" } else { "NO" };
-    println!("{result}");
-    Ok(())
+    problem::run::<TransposingMatrices>()
 }
 
 #[derive(Debug)]
@@ -33,48 +24,26 @@ struct Matrix {
     rows: Vec<Vec<u32>>,
 }
 
-impl Problem<bool> for TransposingMatrices {
-    fn from_reader(reader: impl BufRead) -> Result<Self> {
-        fn split_line(line: &str) -> Result<Vec<u32>> {
-            let result = line
-                .split_whitespace()
-                .map(|integer| integer.parse::<u32>())
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(result)
-        }
-
-        fn read_rows(lines: &[String], lines_range: Range<usize>) -> Result<Matrix> {
-            let rows = lines_range
-                .map(|index| {
-                    let row = lines
-                        .get(index)
-                        .ok_or_else(|| format!("Missing {index} line"))?;
-                    split_line(row)
+impl Scan for TransposingMatrices {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        fn read_rows(
+            scanner: &mut impl Scanner,
+            row_count: usize,
+            column_count: usize,
+        ) -> scanner::Result<Matrix> {
+            let rows = (0..row_count)
+                .map(|_| {
+                    scanner
+                        .next_line()?
+                        .next_vec(column_count, 1..=10u32.pow(9))
                 })
-                .collect::<Result<Vec<_>, _>>()?;
-
-            for row in &rows {
-                for element in row {
-                    debug_assert!((1..=10u32.pow(9)).contains(element))
-                }
-            }
-
+                .collect::<scanner::Result<Vec<_>>>()?;
             Ok(Matrix { rows })
         }
 
-        let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
-
-        let first_line = lines.get(0).ok_or("Missing first line")?;
-        let first_line = split_line(first_line)?;
-
-        let row_count = *first_line.first().ok_or("Missing row count")? as usize;
-        let column_count = *first_line.get(1).ok_or("Missing column count")? as usize;
-
-        debug_assert!((1..=500).contains(&row_count));
-        debug_assert!((1..=500).contains(&column_count));
-
-        let before = read_rows(&lines, 1..row_count + 1)?;
-        let after = read_rows(&lines, row_count + 1..2 * row_count + 1)?;
+        let (row_count, column_count) = scanner.next_line()?.next_tuple((1..=500, 1..=500))?;
+        let before = read_rows(scanner, row_count, column_count)?;
+        let after = read_rows(scanner, row_count, column_count)?;
 
         Ok(TransposingMatrices {
             row_count,
@@ -83,7 +52,18 @@ impl Problem<bool> for TransposingMatrices {
             after,
         })
     }
+}
+
+impl Problem for TransposingMatrices {
+    type Input = Self;
+    type Output = Verdict;
+
+    fn solve(input: Self) -> Result<Verdict> {
+        input.solve().map(Verdict::yes_no)
+    }
+}
 
+impl TransposingMatrices {
     /// Returns true if self.after can be achieved by transpositions in self.before.
     fn solve(self) -> Result<bool> {
         for index in 0..self.column_count + self.row_count - 1 {
@@ -135,6 +115,7 @@ impl Matrix {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
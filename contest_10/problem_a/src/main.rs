@@ -1,39 +1,12 @@
-use std::io;
-use std::io::{BufRead, Lines};
-
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let problem = Line::from_lines(lines)?;
-    let result = problem.solve();
-    println!("{result}");
-    Ok(())
+    problem::run::<Line>()
 }
 
 struct Line {
@@ -41,16 +14,27 @@ struct Line {
     points: Vec<i64>,
 }
 
-impl Line {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        parse_next! { lines =>
+impl Scan for Line {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next! { scanner =>
             let points_count: i64 = 1..=10i64.pow(5);
             let distance: i64 = 1..=10i64.pow(9)
         }
-        parse_next!(lines => let points: Vec<i64> = [1..=10i64.pow(9); points_count]);
+        parse_next!(scanner => let points: Vec<i64> = [-(10i64.pow(9))..=10i64.pow(9); points_count]);
         Ok(Line { distance, points })
     }
+}
 
+impl Problem for Line {
+    type Input = Self;
+    type Output = i64;
+
+    fn solve(input: Self) -> Result<i64> {
+        Ok(input.solve())
+    }
+}
+
+impl Line {
     fn solve(self) -> i64 {
         let length = self.points.len();
         if length < 3 {
@@ -1,32 +1,32 @@
-use std::io;
-use std::io::{BufRead, Lines};
+use output::{Spelling, Verdict};
+use problem::Problem;
+use scanner::Scan;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let problem = Game::from_lines(lines)?;
-    let result = problem.solve()?;
-    if result {
-        println!("First");
-    } else {
-        println!("Second");
-    }
-    Ok(())
+    problem::run::<Game>()
 }
 
+#[derive(Scan)]
 struct Game {
     string: String,
 }
 
-impl Game {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        let string = lines.next().ok_or("Missing line")??;
-        Ok(Game { string })
+impl Problem for Game {
+    type Input = Self;
+    type Output = Verdict;
+
+    fn solve(input: Self) -> Result<Verdict> {
+        Ok(Verdict::new(input.solve()?, WINNER))
     }
+}
+
+/// Whether the first player wins.
+const WINNER: Spelling = Spelling::new("First", "Second");
 
+impl Game {
     fn solve(self) -> Result<bool> {
         let letters = self.count_letters();
         let odds = letters.iter().filter(|count| *count % 2 == 1).count();
@@ -44,6 +44,7 @@ impl Game {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -1,68 +1,69 @@
-use std::io;
+use std::ops::RangeInclusive;
 
-macro_rules! parse_next {
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-    ($line:ident -> $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let mut line = $line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use output::{Spelling, Verdict};
+use parse_next::parse_next;
+use problem::{TestCase, TestCases};
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let mut lines = stdin.lines();
-    parse_next!(lines => let _test_cases: u8 = 1..=100);
-    for line in lines {
-        let problem = NumberGame::from_line(line?)?;
-        let result = problem.solve()?;
-        if result {
-            println!("Ashishgup");
-        } else {
-            println!("FastestFinger");
-        }
-    }
-    Ok(())
+    problem::run::<TestCases<NumberGame>>()
 }
 
+/// Whether the first player wins.
+const WINNER: Spelling = Spelling::new("Ashishgup", "FastestFinger");
+
 struct NumberGame(u32);
 
-impl NumberGame {
-    fn from_line(line: String) -> Result<Self> {
-        parse_next!(line -> let n: u32 = 1..=10u32.pow(9));
+impl Scan for NumberGame {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next!(scanner => let n: u32 = 1..=10u32.pow(9));
         Ok(NumberGame(n))
     }
+}
+
+impl TestCase for NumberGame {
+    type Input = Self;
+    type Output = Verdict;
+    const COUNT: RangeInclusive<u32> = 1..=100;
+
+    fn solve(input: Self) -> Result<Verdict> {
+        Ok(Verdict::new(input.solve()?, WINNER))
+    }
+}
 
+impl NumberGame {
     /// Returns true if the first player wins.
-    fn solve(self) -> Result<bool> {
-        let (twos, odd_part) = self.extract_twos();
-        debug_assert!(odd_part > 0);
+    fn solve(&self) -> Result<bool> {
+        let (twos, odd_part) = number_theory::split_twos(u64::from(self.0));
         if odd_part == 1 {
             Ok(twos == 1)
         } else if twos == 1 {
-            Ok(!Self::is_prime(odd_part))
+            Ok(!number_theory::is_prime(odd_part))
         } else {
             Ok(true)
         }
     }
 
+    /// Solves the game like [`Self::solve`], extracting the twos one by one and testing the
+    /// primality by trial division.
+    #[cfg(test)]
+    fn solve_by_trial_division(&self) -> bool {
+        let (twos, odd_part) = self.extract_twos();
+        debug_assert!(odd_part > 0);
+        if odd_part == 1 {
+            twos == 1
+        } else if twos == 1 {
+            !Self::is_prime(odd_part)
+        } else {
+            true
+        }
+    }
+
     /// Returns number of twos and the remaining odd part.
+    #[cfg(test)]
     fn extract_twos(&self) -> (u8, u32) {
         let mut twos = 0;
         let mut remaining = self.0;
@@ -73,6 +74,7 @@ impl NumberGame {
         (twos, remaining)
     }
 
+    #[cfg(test)]
     fn is_prime(n: u32) -> bool {
         let mut divisor = 2;
         while divisor * divisor <= n {
@@ -86,6 +88,7 @@ impl NumberGame {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -100,4 +103,14 @@ mod tests {
         assert_eq!(true, NumberGame(12).solve()?);
         Ok(())
     }
+
+    #[test]
+    fn test_trial_division() -> Result<()> {
+        let large = (10u32.pow(9) - 2_000..=10u32.pow(9)).chain([2 * 999_999_937, 4 * 249_999_977]);
+        for n in (1..=100_000).chain(large) {
+            let game = NumberGame(n);
+            assert_eq!(game.solve_by_trial_division(), game.solve()?, "{n}");
+        }
+        Ok(())
+    }
 }
@@ -1,42 +1,13 @@
-use std::io;
-use std::io::{BufRead, Lines};
-
-macro_rules! parse_next {
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-    ($line:ident -> $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let mut line = $line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use output::Verdict;
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let problem = Fax::from_lines(lines)?;
-    let result = problem.solve()?;
-    if result {
-        println!("YES");
-    } else {
-        println!("NO");
-    }
-    Ok(())
+    problem::run::<Fax>()
 }
 
 struct Fax {
@@ -44,16 +15,27 @@ struct Fax {
     palindromes: usize,
 }
 
-impl Fax {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        let string = lines.next().ok_or("Missing line")??;
-        parse_next!(lines => let palindromes: usize = 1..=1000);
+impl Scan for Fax {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next!(scanner => let string: String = [len 1..=1000]);
+        parse_next!(scanner => let palindromes: usize = 1..=1000);
         Ok(Fax {
             string,
             palindromes,
         })
     }
+}
+
+impl Problem for Fax {
+    type Input = Self;
+    type Output = Verdict;
+
+    fn solve(input: Self) -> Result<Verdict> {
+        input.solve().map(Verdict::yes_no)
+    }
+}
 
+impl Fax {
     fn solve(self) -> Result<bool> {
         if self.string.len() % self.palindromes != 0 {
             return Ok(false);
@@ -77,6 +59,7 @@ impl Fax {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -1,43 +1,37 @@
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 use std::collections::{HashMap, HashSet};
-use std::io;
-use std::io::{BufRead, Lines};
-
-macro_rules! parse_next {
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let problem = Pokemons::from_lines(lines)?;
-    let result = problem.solve()?;
-    println!("{result}");
-    Ok(())
+    problem::run::<Pokemons>()
 }
 
 struct Pokemons {
     pokemons: String,
 }
 
-impl Pokemons {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        parse_next!(lines => let flat_count: usize = 1..=100_000);
-        let pokemons = lines.next().ok_or("Missing pokemons line")??;
+impl Scan for Pokemons {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next!(scanner => let flat_count: usize = 1..=100_000);
+        parse_next!(scanner => let pokemons: String = [len flat_count..=flat_count]);
         Ok(Pokemons { pokemons })
     }
+}
+
+impl Problem for Pokemons {
+    type Input = Self;
+    type Output = usize;
 
+    fn solve(input: Self) -> Result<usize> {
+        input.solve()
+    }
+}
+
+impl Pokemons {
     fn solve(self) -> Result<usize> {
         let pokemons = self.pokemons.as_bytes();
         let type_count = pokemons.iter().collect::<HashSet<_>>().len();
@@ -50,12 +44,16 @@ impl Pokemons {
         let mut right_iterator = pokemons.iter();
         loop {
             while visited_types.len() < type_count {
-                let Some(right) = right_iterator.next() else { break; };
+                let Some(right) = right_iterator.next() else {
+                    break;
+                };
                 *visited_types.entry(right).or_default() += 1;
                 visited += 1;
             }
             while visited_types.len() == type_count {
-                let Some(left) = left_iterator.next() else { break; };
+                let Some(left) = left_iterator.next() else {
+                    break;
+                };
                 let entry = visited_types
                     .get_mut(left)
                     .expect("Inserted by right iterator");
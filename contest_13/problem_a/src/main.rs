@@ -1,53 +1,31 @@
-use std::io;
-use std::ops::BitOr;
+use std::ops::{BitOr, RangeInclusive};
 
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-    ($line:ident -> $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let mut line = $line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use problem::{TestCase, TestCases};
+use scanner::Scan;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let mut lines = stdin.lines();
-    parse_next!(lines => let _cases: u16 = 1..=1000);
-    while let Some(line) = lines.next() {
-        let line = line?;
-        parse_next!(line -> let number_count: usize = 2..=100);
-        parse_next!(lines => let numbers: Vec<u32> = [0..=2u32.pow(30); number_count]);
-        let result = solve(numbers);
-        println!("{result}");
+    problem::run::<TestCases<Numbers>>()
+}
+
+#[derive(Scan)]
+struct Numbers {
+    #[scan(range = 2..=100)]
+    number_count: usize,
+    #[scan(len = number_count, range = 0..=2u32.pow(30))]
+    numbers: Vec<u32>,
+}
+
+impl TestCase for Numbers {
+    type Input = Self;
+    type Output = u32;
+    const COUNT: RangeInclusive<u32> = 1..=1000;
+
+    fn solve(input: Self) -> Result<u32> {
+        Ok(solve(input.numbers))
     }
-    Ok(())
 }
 
 fn solve(numbers: Vec<u32>) -> u32 {
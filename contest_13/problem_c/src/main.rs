@@ -1,68 +1,53 @@
-use std::{cmp, io, mem};
+use std::ops::RangeInclusive;
+use std::{cmp, mem};
 
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-    ($line:ident -> $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let mut line = $line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use output::OrMinusOne;
+use parse_next::parse_next;
+use problem::{TestCase, TestCases};
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let mut lines = stdin.lines();
-    parse_next!(lines => let _tests: u16 = 1..=1000);
-    while let Some(line) = lines.next() {
-        let line = line?;
-        parse_next!(line -> let bag: u64 = 1..=10u64.pow(18));
-        parse_next!(line -> let box_count: usize = 1..=10usize.pow(5));
-        let boxes = lines.next().ok_or("Missing next line")??;
-        let result = solve(bag, boxes).map(|result| result as i32).unwrap_or(-1);
-        println!("{result}");
+    problem::run::<TestCases<Bag>>()
+}
+
+struct Bag {
+    size: u64,
+    boxes: Vec<u32>,
+}
+
+impl Scan for Bag {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next! { scanner =>
+            let size: u64 = 1..=10u64.pow(18);
+            let box_count: usize = 1..=10usize.pow(5)
+        }
+        parse_next!(scanner => let boxes: Vec<u32> = [1..=10u32.pow(9); box_count]);
+        debug_assert!(boxes.iter().all(|size| size.is_power_of_two()));
+        Ok(Bag { size, boxes })
+    }
+}
+
+impl TestCase for Bag {
+    type Input = Self;
+    type Output = OrMinusOne<u32>;
+    const COUNT: RangeInclusive<u32> = 1..=1000;
+
+    fn solve(input: Self) -> Result<OrMinusOne<u32>> {
+        Ok(OrMinusOne(solve(input.size, &input.boxes)))
     }
-    Ok(())
 }
 
-fn solve(bag: u64, boxes: String) -> Option<u32> {
+fn solve(bag: u64, boxes: &[u32]) -> Option<u32> {
     let bag_length = mem::size_of_val(&bag) * 8 - bag.leading_zeros() as usize;
     let boxes_length = cmp::max(mem::size_of::<u32>() * 8, bag_length);
 
-    let mut boxes =
-        boxes
-            .split_whitespace()
-            .fold(vec![0; boxes_length + 1], |mut boxes, current| {
-                let current = current.parse::<u32>().expect("Input is correct");
-                let index = current.trailing_zeros() as usize;
-                boxes[index] += 1;
-                boxes
-            });
+    let mut counts = vec![0; boxes_length + 1];
+    for size in boxes {
+        counts[size.trailing_zeros() as usize] += 1;
+    }
 
     let mut all_divisions = 0;
     let mut divisions = 0;
@@ -72,24 +57,24 @@ fn solve(bag: u64, boxes: String) -> Option<u32> {
         let mut increment_divisions = false;
 
         if divisions > 0 {
-            if boxes[shift] > 0 {
+            if counts[shift] > 0 {
                 all_divisions += divisions;
                 divisions = 0;
-                boxes[shift] -= 1;
+                counts[shift] -= 1;
             } else {
                 increment_divisions = true;
             }
         }
 
         if (bag >> shift) & 0b1 == 1 {
-            if boxes[shift] > 0 {
-                boxes[shift] -= 1;
+            if counts[shift] > 0 {
+                counts[shift] -= 1;
             } else {
                 increment_divisions = true;
             }
         }
 
-        boxes[shift + 1] += boxes[shift] / 2;
+        counts[shift + 1] += counts[shift] / 2;
         divisions += u32::from(increment_divisions);
         shift += 1;
     }
@@ -103,19 +88,19 @@ mod tests {
 
     #[test]
     fn test_example_1() {
-        let actual = solve(10, "1 32 1".to_owned());
+        let actual = solve(10, &[1, 32, 1]);
         assert_eq!(Some(2), actual);
     }
 
     #[test]
     fn test_example_2() {
-        let actual = solve(23, "16 1 4 1".to_owned());
+        let actual = solve(23, &[16, 1, 4, 1]);
         assert_eq!(None, actual);
     }
 
     #[test]
     fn test_example_3() {
-        let actual = solve(20, "2 1 16 1 8".to_owned());
+        let actual = solve(20, &[2, 1, 16, 1, 8]);
         assert_eq!(Some(0), actual);
     }
 }
@@ -1,118 +1,213 @@
-use std::io;
-use std::io::{Lines, StdinLock};
-
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-    ($line:ident -> $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let mut line = $line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use geometry::{Point, Rectangle};
+use output::Verdict;
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
+const BLACK_COUNT: usize = 2;
+
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let mut lines = stdin.lines();
-    let white = Rectangle::parse(&mut lines)?;
-    let first_black = Rectangle::parse(&mut lines)?;
-    let second_black = Rectangle::parse(&mut lines)?;
-    let result = solve(white, first_black, second_black);
-    if result {
-        println!("YES");
-    } else {
-        println!("NO");
-    }
-    Ok(())
+    problem::run::<Sheets>()
+}
+
+#[derive(Clone, Debug)]
+struct Sheets {
+    white: Rectangle<u64>,
+    blacks: Vec<Rectangle<u64>>,
+}
+
+impl Scan for Sheets {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        let white = scan_rectangle(scanner)?;
+        let blacks = (0..BLACK_COUNT)
+            .map(|_| scan_rectangle(scanner))
+            .collect::<scanner::Result<_>>()?;
+        Ok(Sheets { white, blacks })
+    }
+}
+
+impl Problem for Sheets {
+    type Input = Self;
+    type Output = Verdict;
+
+    fn solve(input: Self) -> Result<Verdict> {
+        let visible = solve(&input.white, &input.blacks);
+        Ok(Verdict::yes_no(visible))
+    }
+}
+
+/// Reads a line with the bottom left and the top right corner of a sheet.
+fn scan_rectangle(scanner: &mut impl Scanner) -> scanner::Result<Rectangle<u64>> {
+    parse_next! { scanner =>
+        let bottom_left_x: u32 = 0..=10u32.pow(6);
+        let bottom_left_y: u32 = 0..=10u32.pow(6);
+        let top_right_x: u32 = bottom_left_x + 1..=10u32.pow(6);
+        let top_right_y: u32 = bottom_left_y + 1..=10u32.pow(6)
+    }
+    let bottom_left = Point::new(bottom_left_x, bottom_left_y).map(u64::from);
+    let top_right = Point::new(top_right_x, top_right_y).map(u64::from);
+    Ok(Rectangle::new(bottom_left, top_right))
+}
+
+/// Returns whether the black sheets leave a part of the white one uncovered, comparing the area of
+/// the white sheet with the area of its part covered by any black sheet.
+fn solve(white: &Rectangle<u64>, blacks: &[Rectangle<u64>]) -> bool {
+    covered_area(white, blacks) < white.area()
 }
 
-struct Rectangle {
-    bottom_left: (u32, u32),
-    top_right: (u32, u32),
+/// Returns the area of the union of the parts of the black sheets lying on the white one.
+///
+/// A vertical line sweeps over the left and right edges of the parts, keeping the length of the
+/// covered part of the line in a [`Coverage`] tree over the compressed `y` coordinates of the
+/// parts, so that between two edges the covered area grows by that length times the distance.
+fn covered_area(white: &Rectangle<u64>, blacks: &[Rectangle<u64>]) -> u64 {
+    let parts = blacks
+        .iter()
+        .filter_map(|black| white.intersection(black))
+        .filter(|part| part.area() > 0)
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        return 0;
+    }
+
+    let mut ys = parts
+        .iter()
+        .flat_map(|part| [part.min.y, part.max.y])
+        .collect::<Vec<_>>();
+    ys.sort_unstable();
+    ys.dedup();
+    let index = |y: u64| {
+        ys.binary_search(&y)
+            .expect("the edges of the parts are compressed")
+    };
+
+    let mut edges = Vec::with_capacity(2 * parts.len());
+    for part in &parts {
+        let (bottom, top) = (index(part.min.y), index(part.max.y));
+        edges.push((part.min.x, 1, bottom, top));
+        edges.push((part.max.x, -1, bottom, top));
+    }
+    edges.sort_unstable();
+
+    let mut coverage = Coverage::new(&ys);
+    let mut area = 0;
+    let mut previous_x = edges[0].0;
+    for (x, change, bottom, top) in edges {
+        area += coverage.covered_length() * (x - previous_x);
+        coverage.add(bottom, top, change);
+        previous_x = x;
+    }
+    debug_assert_eq!(0, coverage.covered_length());
+    area
 }
 
-impl Rectangle {
-    pub fn new(bottom_left: (u32, u32), top_right: (u32, u32)) -> Self {
+/// Segment tree over the elementary intervals between consecutive compressed coordinates, counting
+/// how many intervals added whole cover every node and the covered length below it.
+struct Coverage<'a> {
+    coordinates: &'a [u64],
+    counts: Vec<i32>,
+    covered: Vec<u64>,
+}
+
+impl<'a> Coverage<'a> {
+    fn new(coordinates: &'a [u64]) -> Self {
+        debug_assert!(coordinates.len() >= 2);
+        let size = 4 * coordinates.len();
         Self {
-            bottom_left,
-            top_right,
+            coordinates,
+            counts: vec![0; size],
+            covered: vec![0; size],
         }
     }
 
-    pub fn parse(lines: &mut Lines<StdinLock>) -> Result<Self> {
-        parse_next! { lines =>
-            let bottom_left_x: u32 = 0..=10u32.pow(6);
-            let bottom_left_y: u32 = 0..=10u32.pow(6);
-            let top_right_x: u32 = 0..=10u32.pow(6);
-            let top_right_y: u32 = 0..=10u32.pow(6)
-        }
-        Ok(Self::new(
-            (bottom_left_x, bottom_left_y),
-            (top_right_x, top_right_y),
-        ))
+    /// Returns the length covered by at least one added interval.
+    fn covered_length(&self) -> u64 {
+        self.covered[1]
     }
 
-    pub fn contains(&self, point: (u32, u32)) -> bool {
-        point.0 >= self.bottom_left.0
-            && point.0 <= self.top_right.0
-            && point.1 >= self.bottom_left.1
-            && point.1 <= self.top_right.1
+    /// Adds, with `change` 1, or removes, with -1, the interval between the coordinates at the
+    /// indices `low` and `high`.
+    fn add(&mut self, low: usize, high: usize, change: i32) {
+        let elementary_count = self.coordinates.len() - 1;
+        self.update(1, 0, elementary_count, low, high, change);
     }
 
-    pub fn scale(&mut self, multiplier: u32) {
-        self.bottom_left.0 *= multiplier;
-        self.bottom_left.1 *= multiplier;
-        self.top_right.0 *= multiplier;
-        self.top_right.1 *= multiplier;
+    /// Updates the node of the elementary intervals `left..right`.
+    fn update(
+        &mut self,
+        node: usize,
+        left: usize,
+        right: usize,
+        low: usize,
+        high: usize,
+        change: i32,
+    ) {
+        if high <= left || right <= low {
+            return;
+        }
+        if low <= left && right <= high {
+            self.counts[node] += change;
+            debug_assert!(self.counts[node] >= 0);
+        } else {
+            let middle = (left + right) / 2;
+            self.update(2 * node, left, middle, low, high, change);
+            self.update(2 * node + 1, middle, right, low, high, change);
+        }
+        self.covered[node] = if self.counts[node] > 0 {
+            self.coordinates[right] - self.coordinates[left]
+        } else if right - left == 1 {
+            0
+        } else {
+            self.covered[2 * node] + self.covered[2 * node + 1]
+        };
+    }
+}
+
+/// Solves like [`solve`], computing the area covered by the black sheets by the inclusion–exclusion
+/// principle, adding and subtracting the common parts of all subsets of the black sheets.
+#[cfg(test)]
+fn solve_by_inclusion_exclusion(white: &Rectangle<u64>, blacks: &[Rectangle<u64>]) -> bool {
+    let mut covered = 0i64;
+    for subset in 1..1usize << blacks.len() {
+        let common = blacks
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| subset >> index & 1 == 1)
+            .try_fold(*white, |common, (_, black)| common.intersection(black));
+        let area = common.map_or(0, |common| common.area() as i64);
+        if subset.count_ones() % 2 == 1 {
+            covered += area;
+        } else {
+            covered -= area;
+        }
     }
+    covered < white.area() as i64
 }
 
-fn solve(mut white: Rectangle, mut first_black: Rectangle, mut second_black: Rectangle) -> bool {
-    white.scale(2);
-    first_black.scale(2);
-    second_black.scale(2);
-
-    for x in (white.bottom_left.0)..=(white.top_right.0) {
-        let y_top = white.top_right.1;
-        let y_bottom = white.bottom_left.1;
-        if (!first_black.contains((x, y_top)) && !second_black.contains((x, y_top)))
-            || (!first_black.contains((x, y_bottom)) && !second_black.contains((x, y_bottom)))
-        {
+/// Looks for an uncovered point on the boundary of the white sheet, walking along it in steps of
+/// half a unit. Two black sheets covering the boundary cover the whole white sheet, but more could
+/// leave a hole inside.
+#[cfg(test)]
+fn solve_by_perimeter_walk(white: &Rectangle<u64>, blacks: &[Rectangle<u64>]) -> bool {
+    debug_assert!(blacks.len() <= 2);
+    let white = white.scale(2);
+    let blacks = blacks
+        .iter()
+        .map(|black| black.scale(2))
+        .collect::<Vec<_>>();
+    let uncovered = |point: Point<u64>| !blacks.iter().any(|black| black.contains(point));
+
+    for x in white.min.x..=white.max.x {
+        if uncovered(Point::new(x, white.max.y)) || uncovered(Point::new(x, white.min.y)) {
             return true;
         }
     }
 
-    for y in (white.bottom_left.1)..=(white.top_right.1) {
-        let x_right = white.top_right.0;
-        let x_left = white.bottom_left.0;
-        if (!first_black.contains((x_right, y)) && !second_black.contains((x_right, y)))
-            || (!first_black.contains((x_left, y)) && !second_black.contains((x_left, y)))
-        {
+    for y in white.min.y..=white.max.y {
+        if uncovered(Point::new(white.max.x, y)) || uncovered(Point::new(white.min.x, y)) {
             return true;
         }
     }
@@ -121,16 +216,21 @@ fn solve(mut white: Rectangle, mut first_black: Rectangle, mut second_black: Rec
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
+    use stress::{Random, Stress};
+
     use super::*;
-    use std::assert_eq;
+
+    fn rectangle(bottom_left: (u64, u64), top_right: (u64, u64)) -> Rectangle<u64> {
+        Rectangle::new(Point::from(bottom_left), Point::from(top_right))
+    }
 
     #[test]
     fn test_example_1() {
         let actual = solve(
-            Rectangle::new((2, 2), (4, 4)),
-            Rectangle::new((1, 1), (3, 5)),
-            Rectangle::new((3, 1), (5, 5)),
+            &rectangle((2, 2), (4, 4)),
+            &[rectangle((1, 1), (3, 5)), rectangle((3, 1), (5, 5))],
         );
         assert_eq!(false, actual);
     }
@@ -138,9 +238,8 @@ mod tests {
     #[test]
     fn test_example_2() {
         let actual = solve(
-            Rectangle::new((3, 3), (7, 5)),
-            Rectangle::new((0, 0), (4, 6)),
-            Rectangle::new((0, 0), (7, 4)),
+            &rectangle((3, 3), (7, 5)),
+            &[rectangle((0, 0), (4, 6)), rectangle((0, 0), (7, 4))],
         );
         assert_eq!(true, actual);
     }
@@ -148,9 +247,8 @@ mod tests {
     #[test]
     fn test_example_3() {
         let actual = solve(
-            Rectangle::new((5, 2), (10, 5)),
-            Rectangle::new((3, 1), (7, 6)),
-            Rectangle::new((8, 1), (11, 7)),
+            &rectangle((5, 2), (10, 5)),
+            &[rectangle((3, 1), (7, 6)), rectangle((8, 1), (11, 7))],
         );
         assert_eq!(true, actual);
     }
@@ -158,10 +256,129 @@ mod tests {
     #[test]
     fn test_example_4() {
         let actual = solve(
-            Rectangle::new((0, 0), (1000000, 1000000)),
-            Rectangle::new((0, 0), (499999, 1000000)),
-            Rectangle::new((500000, 0), (1000000, 1000000)),
+            &rectangle((0, 0), (1000000, 1000000)),
+            &[
+                rectangle((0, 0), (499999, 1000000)),
+                rectangle((500000, 0), (1000000, 1000000)),
+            ],
         );
         assert_eq!(true, actual);
     }
+
+    #[test]
+    fn test_hole() {
+        let white = rectangle((0, 0), (3, 3));
+        let frame = [
+            rectangle((0, 0), (3, 1)),
+            rectangle((0, 2), (3, 3)),
+            rectangle((0, 0), (1, 3)),
+            rectangle((2, 0), (3, 3)),
+        ];
+        assert!(solve(&white, &frame));
+        assert!(!solve(
+            &white,
+            &[&frame[..], &[rectangle((1, 1), (2, 2))]].concat()
+        ));
+        assert!(solve(&white, &[]));
+    }
+
+    #[test]
+    fn test_covered_area() {
+        let white = rectangle((0, 0), (10, 10));
+        let blacks = (0..10)
+            .map(|index| rectangle((index, index), (index + 2, index + 2)))
+            .collect::<Vec<_>>();
+        assert_eq!(3 * 9 + 1, covered_area(&white, &blacks));
+        let large = rectangle((0, 0), (1000000, 1000000));
+        assert_eq!(10u64.pow(12), covered_area(&large, &[large, large]));
+    }
+
+    fn random_rectangle(random: &mut Random, size: usize) -> Rectangle<u64> {
+        let mut coordinate_pair = || {
+            let mut pair = random.distinct(2, 0..=size as u64);
+            pair.sort_unstable();
+            (pair[0], pair[1])
+        };
+        let (left, right) = coordinate_pair();
+        let (bottom, top) = coordinate_pair();
+        rectangle((left, bottom), (right, top))
+    }
+
+    /// Returns the sheets with one of the black ones removed.
+    fn without_a_black(sheets: &Sheets) -> Vec<Sheets> {
+        stress::removals(&sheets.blacks)
+            .into_iter()
+            .map(|blacks| Sheets {
+                white: sheets.white,
+                blacks,
+            })
+            .collect()
+    }
+
+    fn random_sheets(random: &mut Random, size: usize, black_count: usize) -> Sheets {
+        Sheets {
+            white: random_rectangle(random, size),
+            blacks: (0..black_count)
+                .map(|_| random_rectangle(random, size))
+                .collect(),
+        }
+    }
+
+    /// Compares the sweep with the perimeter walk on two black sheets.
+    struct PerimeterWalk;
+
+    impl Stress for PerimeterWalk {
+        type Input = Sheets;
+        type Output = bool;
+        const MAX_SIZE: usize = 20;
+
+        fn generate(random: &mut Random, size: usize) -> Sheets {
+            random_sheets(random, size, BLACK_COUNT)
+        }
+
+        fn reference(input: &Sheets) -> bool {
+            solve_by_perimeter_walk(&input.white, &input.blacks)
+        }
+
+        fn solve(input: &Sheets) -> bool {
+            solve(&input.white, &input.blacks)
+        }
+
+        fn shrink(input: &Sheets) -> Vec<Sheets> {
+            without_a_black(input)
+        }
+    }
+
+    /// Compares the sweep with the inclusion–exclusion on up to eight black sheets, which may
+    /// cover the boundary of the white one and still leave a hole.
+    struct InclusionExclusion;
+
+    impl Stress for InclusionExclusion {
+        type Input = Sheets;
+        type Output = bool;
+        const MAX_SIZE: usize = 8;
+
+        fn generate(random: &mut Random, size: usize) -> Sheets {
+            let black_count = random.range(0..=size);
+            random_sheets(random, size, black_count)
+        }
+
+        fn reference(input: &Sheets) -> bool {
+            solve_by_inclusion_exclusion(&input.white, &input.blacks)
+        }
+
+        fn solve(input: &Sheets) -> bool {
+            solve(&input.white, &input.blacks)
+        }
+
+        fn shrink(input: &Sheets) -> Vec<Sheets> {
+            without_a_black(input)
+        }
+    }
+
+    #[test]
+    fn test_stress() {
+        stress::check::<PerimeterWalk>(5000, 0);
+        stress::check::<InclusionExclusion>(5000, 0);
+    }
 }
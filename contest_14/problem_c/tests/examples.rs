@@ -0,0 +1 @@
+harness::test_examples!(checker::Floats::new(1e-6));
@@ -1,55 +1,34 @@
-use std::io;
+use std::ops::RangeInclusive;
 
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-    ($line:ident -> $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let mut line = $line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use output::Words;
+use parse_next::parse_next;
+use problem::{TestCase, TestCases};
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let mut lines = stdin.lines();
-    parse_next!(lines => let _test_cases: u8 = 1..=100);
-    for line in lines {
-        let line = line?;
-        parse_next!(line -> let cats: usize = 2..=100);
-        let result = solve(cats)
-            .into_iter()
-            .map(|number| number.to_string())
-            .collect::<Vec<_>>()
-            .join(" ");
-        println!("{result}");
+    problem::run::<TestCases<Cats>>()
+}
+
+struct Cats(usize);
+
+impl Scan for Cats {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next!(scanner => let cats: usize = 2..=100);
+        Ok(Cats(cats))
+    }
+}
+
+impl TestCase for Cats {
+    type Input = Self;
+    type Output = Words<usize>;
+    const COUNT: RangeInclusive<u32> = 1..=100;
+
+    fn solve(input: Self) -> Result<Words<usize>> {
+        Ok(Words(solve(input.0)))
     }
-    Ok(())
 }
 
 fn solve(cats: usize) -> Vec<usize> {
@@ -0,0 +1,56 @@
+use checker::{Checker, Tokens};
+
+harness::test_examples!(check_permutations);
+
+/// Accepts any permutations moving every cat and with the same total distance as the expected ones.
+fn check_permutations(input: &str, expected: &str, actual: &str) -> Result<(), String> {
+    let mut input = Tokens::new(input);
+    let mut expected = Tokens::new(expected);
+    let mut actual = Tokens::new(actual);
+    let case_count = input.parse::<usize>()?;
+    for _ in 0..case_count {
+        let cats = input.parse::<usize>()?;
+        let mut seen = vec![false; cats];
+        let (mut expected_distance, mut actual_distance) = (0, 0);
+        for position in 1..=cats {
+            expected_distance += expected.parse::<usize>()?.abs_diff(position);
+            let cat = actual.parse::<usize>()?;
+            if cat == position {
+                return Err(actual.error(format_args!("cat {cat} did not move")));
+            }
+            if !(1..=cats).contains(&cat) || seen[cat - 1] {
+                return Err(actual.error(format_args!("{cat} is not a remaining cat")));
+            }
+            seen[cat - 1] = true;
+            actual_distance += cat.abs_diff(position);
+        }
+        if actual_distance != expected_distance {
+            return Err(actual.error(format_args!(
+                "the cats moved by {actual_distance} in total instead of {expected_distance}"
+            )));
+        }
+    }
+    actual.finish()
+}
+
+#[test]
+fn test_check_permutations() {
+    let input = "2\n2\n3\n";
+    let expected = "2 1\n3 1 2\n";
+    assert_eq!(
+        Ok(()),
+        check_permutations.check(input, expected, "2 1\n2 3 1\n")
+    );
+    assert_eq!(
+        Err("token 4: cat 2 did not move".to_owned()),
+        check_permutations.check(input, expected, "2 1\n3 2 1\n")
+    );
+    assert_eq!(
+        Err("token 5: 1 is not a remaining cat".to_owned()),
+        check_permutations.check(input, expected, "2 1\n3 1 1\n")
+    );
+    assert_eq!(
+        Err("token 4: the cats moved by 6 in total instead of 4".to_owned()),
+        check_permutations.check("1\n4\n", "2 1 4 3\n", "2 3 4 1\n")
+    );
+}
@@ -1,54 +1,31 @@
-use std::io;
-use std::io::BufRead;
+use problem::Problem;
+use scanner::Scan;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error + 'static>;
 
-trait Problem<T>: Sized {
-    fn from_reader(reader: impl BufRead) -> Result<Self>;
-
-    fn solve(self) -> Result<T>;
-}
-
 fn main() -> Result<()> {
-    let stdin = io::stdin().lock();
-    let problem = Trip::from_reader(stdin)?;
-    let result = problem.solve()?;
-    println!("{result}");
-    Ok(())
+    problem::run::<Trip>()
 }
 
+#[derive(Scan)]
 struct Trip {
+    #[scan(range = 2..=100)]
     city_count: u32,
+    #[scan(same_line, range = 1..=100)]
     tank_capacity: u32,
 }
 
-impl Problem<u32> for Trip {
-    fn from_reader(reader: impl BufRead) -> Result<Self> {
-        fn split_line(line: &str) -> Result<Vec<u32>> {
-            let result = line
-                .split_whitespace()
-                .map(|integer| integer.parse::<u32>())
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(result)
-        }
-
-        let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
-        let first_line = lines.get(0).ok_or("Missing first line")?;
-        let first_line = split_line(first_line)?;
-
-        let city_count = *first_line.first().ok_or("Missing city count")?;
-        let tank_capacity = *first_line.get(1).ok_or("Missing tank capacity")?;
+impl Problem for Trip {
+    type Input = Self;
+    type Output = u32;
 
-        debug_assert!((2..=100).contains(&city_count));
-        debug_assert!((1..=100).contains(&tank_capacity));
-
-        Ok(Trip {
-            city_count,
-            tank_capacity,
-        })
+    fn solve(input: Self) -> Result<u32> {
+        input.solve()
     }
+}
 
+impl Trip {
     /// Returns minimum fuel cost.
     ///
     /// Strategy is to keep the tank at its maximum level in every city,
@@ -1,303 +1,268 @@
-use std::collections::{HashMap, HashSet};
-use std::io;
-use std::io::BufRead;
-use std::str::FromStr;
+use problem::Problem;
+use scanner::{LineScanner, Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error + 'static>;
 
-trait Problem<T>: Sized {
-    fn from_reader(reader: impl BufRead) -> Result<Self>;
-
-    fn solve(self) -> Result<T>;
-}
-
 fn main() -> Result<()> {
-    let stdin = io::stdin().lock();
-    let problem = Toy::from_reader(stdin)?;
-    let result = problem.solve()?;
-    println!("{result}");
-    Ok(())
-}
-
-struct Toy {
-    parts: Graph,
+    problem::run::<Toy>()
 }
 
-type VertexId = usize;
-
-struct Graph {
-    vertices: HashMap<VertexId, Vertex>,
-}
+/// Whether the solution removes the parts greedily instead of summing the ropes. Both ways are
+/// kept, so that the tests cross-check them.
+const GREEDY: bool = false;
 
-struct Vertex {
-    neighbours: HashSet<VertexId>,
-    value: u32,
+#[derive(Clone, Debug)]
+struct Toy {
+    values: Vec<u32>,
+    ropes: Vec<(usize, usize)>,
 }
 
-impl Problem<u32> for Toy {
-    fn from_reader(reader: impl BufRead) -> Result<Self> {
-        fn split_parse_iter<T>(line: &str) -> impl Iterator<Item = Result<T, T::Err>> + '_
-        where
-            T: FromStr,
-            T::Err: std::error::Error,
-        {
-            line.split_whitespace().map(|entry| entry.parse::<T>())
-        }
-
-        let mut lines = reader.lines();
-
-        let first_line = lines.next().ok_or("Missing first line")??;
-        let mut first_line = split_parse_iter(&first_line);
-        let vertex_count = first_line.next().ok_or("Missing vertex count")??;
-        let edge_count = first_line.next().ok_or("Missing edge capacity")??;
+impl Scan for Toy {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        let (part_count, rope_count): (usize, usize) =
+            scanner.next_line()?.next_tuple((1..=1_000, 0..=2_000))?;
 
-        debug_assert!((1..=1_000).contains(&vertex_count));
-        debug_assert!((0..=2_000).contains(&edge_count));
-        debug_assert!(first_line.next().is_none());
+        let values = scanner.next_line()?.next_vec(part_count, 0..=100_000)?;
 
-        let second_line = lines.next().ok_or("Missing second line")??;
-        let second_line = split_parse_iter(&second_line);
-        let vertices = second_line
-            .map(|value| value.map(Vertex::new))
-            .enumerate()
-            .map(|(id, vertex)| vertex.map(|vertex| (id, vertex)))
-            .collect::<Result<HashMap<_, _>, _>>()?;
-
-        debug_assert_eq!(vertices.len(), vertex_count);
-        for vertex in &vertices {
-            debug_assert!((0..=100_000).contains(&vertex.1.value))
+        let mut ropes = Vec::with_capacity(rope_count);
+        for _ in 0..rope_count {
+            let (first_part, second_part): (usize, usize) = scanner
+                .next_line()?
+                .next_tuple((1..=part_count, 1..=part_count))?;
+            debug_assert_ne!(first_part, second_part);
+            ropes.push((first_part - 1, second_part - 1));
         }
 
-        let parts = Graph { vertices };
-        let mut toy = Toy { parts };
-
-        let mut line_counter = 0;
-        for line in lines {
-            let line = line?;
-            let mut edge = split_parse_iter(&line);
-            let first_vertex_id = edge.next().ok_or("Missing first vertex")??;
-            let second_vertex_id = edge.next().ok_or("Missing second vertex")??;
-
-            debug_assert!((1..=vertex_count).contains(&first_vertex_id));
-            debug_assert!((1..=vertex_count).contains(&second_vertex_id));
-            debug_assert_ne!(first_vertex_id, second_vertex_id);
-            debug_assert!(edge.next().is_none());
+        Ok(Toy { values, ropes })
+    }
+}
 
-            toy.parts
-                .insert_edge(first_vertex_id - 1, second_vertex_id - 1);
+impl Problem for Toy {
+    type Input = Self;
+    type Output = u32;
 
-            line_counter += 1;
-        }
-        debug_assert_eq!(line_counter, edge_count);
+    fn solve(input: Self) -> Result<u32> {
+        Ok(input.solve(GREEDY))
+    }
+}
 
-        toy.parts.vertices.shrink_to_fit();
-        for vertex in &mut toy.parts.vertices.values_mut() {
-            vertex.neighbours.shrink_to_fit()
+impl Toy {
+    fn solve(&self, greedy: bool) -> u32 {
+        if greedy {
+            self.solve_greedily()
+        } else {
+            self.sum_ropes()
         }
-
-        Ok(toy)
     }
 
-    fn solve(mut self) -> Result<u32> {
+    /// Removing the most valuable part first never pays for it, so the parts are removed in the
+    /// decreasing order of values, each paying for the neighbours that are still there.
+    fn solve_greedily(&self) -> u32 {
+        let neighbours = Adjacency::new(self.values.len(), &self.ropes);
+        let mut parts = (0..self.values.len()).collect::<Vec<_>>();
+        parts.sort_unstable_by_key(|&part| self.values[part]);
+        let mut removed = vec![false; self.values.len()];
         let mut cost = 0;
-        let mut vertices = (0..self.parts.vertices.len()).collect::<Vec<_>>();
-        vertices.sort_unstable_by_key(|vertex_id| self.parts.vertices[vertex_id].value);
-        while let Some(vertex_id) = vertices.pop() {
-            let vertex = self
-                .parts
-                .vertices
-                .remove(&vertex_id)
-                .ok_or("Missing vertex")?;
-            let mut neighbours_value_sum = 0;
-            for neighbour_id in vertex.neighbours {
-                let neighbour = self
-                    .parts
-                    .vertices
-                    .get_mut(&neighbour_id)
-                    .ok_or("Missing vertex")?;
-                neighbour.neighbours.remove(&vertex_id);
-                neighbours_value_sum += neighbour.value;
-            }
-            cost += neighbours_value_sum;
+        while let Some(part) = parts.pop() {
+            removed[part] = true;
+            cost += neighbours
+                .of(part)
+                .iter()
+                .filter(|&&neighbour| !removed[neighbour])
+                .map(|&neighbour| self.values[neighbour])
+                .sum::<u32>();
         }
-        Ok(cost)
+        cost
     }
-}
-
-impl Graph {
-    fn insert_edge(&mut self, first_vertex_id: VertexId, second_vertex_id: VertexId) {
-        fn insert_neighbour(
-            graph: &mut Graph,
-            first_vertex_id: VertexId,
-            second_vertex_id: VertexId,
-        ) {
-            if let Some(vertex) = graph.vertices.get_mut(&first_vertex_id) {
-                vertex.neighbours.insert(second_vertex_id);
-            } else {
-                debug_assert!(false);
-            }
-        }
 
-        insert_neighbour(self, first_vertex_id, second_vertex_id);
-        insert_neighbour(self, second_vertex_id, first_vertex_id);
+    /// In the greedy order every rope is cut when its more valuable end is removed, paying for
+    /// the lesser end, so the cost is the sum of the lesser ends over all ropes.
+    fn sum_ropes(&self) -> u32 {
+        self.ropes
+            .iter()
+            .map(|&(first, second)| self.values[first].min(self.values[second]))
+            .sum()
     }
 }
 
-impl Vertex {
-    fn new(value: u32) -> Self {
+/// Neighbours of all vertices packed into a single vector, those of vertex `v` lying between
+/// `offsets[v]` and `offsets[v + 1]`.
+struct Adjacency {
+    offsets: Vec<usize>,
+    neighbours: Vec<usize>,
+}
+
+impl Adjacency {
+    fn new(vertex_count: usize, edges: &[(usize, usize)]) -> Self {
+        let mut offsets = vec![0; vertex_count + 1];
+        for &(first, second) in edges {
+            offsets[first + 1] += 1;
+            offsets[second + 1] += 1;
+        }
+        for vertex in 0..vertex_count {
+            offsets[vertex + 1] += offsets[vertex];
+        }
+        let mut neighbours = vec![0; offsets[vertex_count]];
+        let mut next = offsets.clone();
+        for &(first, second) in edges {
+            neighbours[next[first]] = second;
+            next[first] += 1;
+            neighbours[next[second]] = first;
+            next[second] += 1;
+        }
         Self {
-            neighbours: HashSet::new(),
-            value,
+            offsets,
+            neighbours,
         }
     }
+
+    fn of(&self, vertex: usize) -> &[usize] {
+        &self.neighbours[self.offsets[vertex]..self.offsets[vertex + 1]]
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use generator::{Generator, Input, Random, Size};
+    use stress::Stress;
+
     use super::*;
 
+    fn assert_cost(expected: u32, toy: &Toy) {
+        assert_eq!(expected, toy.solve(true), "greedy");
+        assert_eq!(expected, toy.solve(false), "rope sum");
+    }
+
+    #[test]
+    fn test_example_1() {
+        let toy = Toy {
+            values: vec![10, 20, 30, 40],
+            ropes: vec![(0, 3), (0, 1), (1, 2)],
+        };
+        assert_cost(40, &toy);
+    }
+
     #[test]
-    fn test_example_1() -> Result<()> {
-        let problem = Toy {
-            parts: Graph {
-                vertices: HashMap::from([
-                    (
-                        0,
-                        Vertex {
-                            neighbours: HashSet::from([3, 1]),
-                            value: 10,
-                        },
-                    ),
-                    (
-                        1,
-                        Vertex {
-                            neighbours: HashSet::from([0, 2]),
-                            value: 20,
-                        },
-                    ),
-                    (
-                        2,
-                        Vertex {
-                            neighbours: HashSet::from([1]),
-                            value: 30,
-                        },
-                    ),
-                    (
-                        3,
-                        Vertex {
-                            neighbours: HashSet::from([0]),
-                            value: 40,
-                        },
-                    ),
-                ]),
-            },
+    fn test_example_2() {
+        let toy = Toy {
+            values: vec![100, 100, 100, 100],
+            ropes: vec![(0, 1), (1, 2), (1, 3), (2, 3)],
         };
-        let actual = problem.solve()?;
-        assert_eq!(40, actual);
-        Ok(())
+        assert_cost(400, &toy);
     }
 
     #[test]
-    fn test_example_2() -> Result<()> {
-        let problem = Toy {
-            parts: Graph {
-                vertices: HashMap::from([
-                    (
-                        0,
-                        Vertex {
-                            neighbours: HashSet::from([1]),
-                            value: 100,
-                        },
-                    ),
-                    (
-                        1,
-                        Vertex {
-                            neighbours: HashSet::from([0, 2, 3]),
-                            value: 100,
-                        },
-                    ),
-                    (
-                        2,
-                        Vertex {
-                            neighbours: HashSet::from([3, 1]),
-                            value: 100,
-                        },
-                    ),
-                    (
-                        3,
-                        Vertex {
-                            neighbours: HashSet::from([2, 1]),
-                            value: 100,
-                        },
-                    ),
-                ]),
-            },
+    fn test_example_3() {
+        let toy = Toy {
+            values: vec![40, 10, 20, 10, 20, 80, 40],
+            ropes: vec![
+                (0, 4),
+                (3, 6),
+                (3, 4),
+                (4, 1),
+                (4, 6),
+                (5, 3),
+                (0, 5),
+                (0, 2),
+                (3, 2),
+                (0, 3),
+            ],
         };
-        let actual = problem.solve()?;
-        assert_eq!(400, actual);
-        Ok(())
+        assert_cost(160, &toy);
+    }
+
+    impl Stress for Toy {
+        type Input = Self;
+        type Output = u32;
+        const MAX_SIZE: usize = 30;
+
+        fn generate(random: &mut Random, size: usize) -> Self {
+            let part_count = random.range(1..=size);
+            let rope_count = random.range(0..=(part_count * (part_count - 1) / 2).min(2 * size));
+            let ropes = random
+                .simple_graph(part_count, rope_count)
+                .into_iter()
+                .map(|(first, second)| (first - 1, second - 1))
+                .collect();
+            Toy {
+                values: random.array(part_count, 0..=100),
+                ropes,
+            }
+        }
+
+        fn reference(input: &Self) -> u32 {
+            input.solve(true)
+        }
+
+        fn solve(input: &Self) -> u32 {
+            input.solve(false)
+        }
+
+        fn shrink(input: &Self) -> Vec<Self> {
+            stress::removals(&input.ropes)
+                .into_iter()
+                .map(|ropes| Toy {
+                    values: input.values.clone(),
+                    ropes,
+                })
+                .collect()
+        }
     }
 
     #[test]
-    fn test_example_3() -> Result<()> {
-        let problem = Toy {
-            parts: Graph {
-                vertices: HashMap::from([
-                    (
-                        0,
-                        Vertex {
-                            neighbours: HashSet::from([4, 5, 2, 3]),
-                            value: 40,
-                        },
-                    ),
-                    (
-                        1,
-                        Vertex {
-                            neighbours: HashSet::from([4]),
-                            value: 10,
-                        },
-                    ),
-                    (
-                        2,
-                        Vertex {
-                            neighbours: HashSet::from([0, 3]),
-                            value: 20,
-                        },
-                    ),
-                    (
-                        3,
-                        Vertex {
-                            neighbours: HashSet::from([6, 4, 5, 2, 0]),
-                            value: 10,
-                        },
-                    ),
-                    (
-                        4,
-                        Vertex {
-                            neighbours: HashSet::from([0, 3, 1, 6]),
-                            value: 20,
-                        },
-                    ),
-                    (
-                        5,
-                        Vertex {
-                            neighbours: HashSet::from([3, 0]),
-                            value: 80,
-                        },
-                    ),
-                    (
-                        6,
-                        Vertex {
-                            neighbours: HashSet::from([3, 4]),
-                            value: 40,
-                        },
-                    ),
-                ]),
-            },
+    fn test_stress() {
+        stress::check::<Toy>(2000, 0);
+    }
+
+    fn toy(values: Vec<u32>, edges: Vec<(usize, usize)>) -> String {
+        Input::new()
+            .line([values.len(), edges.len()])
+            .line(values)
+            .pairs(edges)
+            .into()
+    }
+
+    /// Up to 10 parts joined by any ropes, or 1000 parts joined by 2000 ropes into one toy.
+    fn sized_toy(random: &mut Random, size: Size) -> String {
+        let vertex_count = size.draw(random, 1..=size.pick(10, 1_000));
+        let values = random.array(vertex_count, 0..=size.pick(10, 100_000));
+        let edges = match size {
+            Size::Small => {
+                let edge_count = random.range(0..=vertex_count * (vertex_count - 1) / 2);
+                random.simple_graph(vertex_count, edge_count)
+            }
+            Size::Maximal => random.connected_graph(vertex_count, 2_000),
         };
-        let actual = problem.solve()?;
-        assert_eq!(160, actual);
-        Ok(())
+        toy(values, edges)
+    }
+
+    /// Parts joined to a single part, the most valuable one, so that removing it first pays for
+    /// nothing but its neighbours.
+    fn star(random: &mut Random) -> String {
+        let mut values = random.array(1_000, 0..=99_999);
+        values[0] = 100_000;
+        let edges = (2..=1_000).map(|leaf| (1, leaf)).collect();
+        toy(values, edges)
+    }
+
+    /// Nearly all pairs of 64 parts joined, the densest graph within the edge limit.
+    fn dense(random: &mut Random) -> String {
+        toy(
+            random.array(64, 0..=100_000),
+            random.simple_graph(64, 2_000),
+        )
+    }
+
+    const GENERATORS: [Generator; 4] = [
+        Generator::sized("random", sized_toy, Size::Small),
+        Generator::sized("maximal", sized_toy, Size::Maximal),
+        Generator::new("star", star),
+        Generator::new("dense", dense),
+    ];
+
+    #[test]
+    fn test_generators() -> Result<()> {
+        generator::check::<Toy>(&GENERATORS, 0..10)
     }
 }
@@ -1,72 +1,35 @@
 use std::collections::VecDeque;
-use std::io;
-use std::io::BufRead;
-use std::str::FromStr;
+use std::ops::RangeInclusive;
+
+use bitset::BitSet;
+use output::OrNo;
+use problem::{TestCase, TestCases};
+use scanner::Scan;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error + 'static>;
 
-trait Problem<T>: Sized {
-    fn from_reader(reader: impl BufRead) -> Result<Self>;
-
-    fn solve(self) -> Result<T>;
-}
-
 fn main() -> Result<()> {
-    let stdin = io::stdin().lock();
-    let mut lines = stdin.lines();
-
-    let problem_count = lines.next().ok_or("Missing problem count line")??;
-    let mut problem_count = split_parse_iter::<usize>(&problem_count);
-    let problem_count = problem_count.next().ok_or("Missing problem count")??;
-    debug_assert!((1..=1_000).contains(&problem_count));
-
-    let mut counter = 0;
-    for line in lines {
-        let password = line?;
-        debug_assert!((1..=200).contains(&password.len()));
-        solve_problem(password)?;
-        counter += 1;
-    }
-    debug_assert_eq!(counter, problem_count);
-    Ok(())
-}
-
-fn solve_problem(password: String) -> Result<()> {
-    let problem = Keyboard::new(password);
-    let result = problem.solve()?;
-    if let Some(password) = result {
-        println!("YES");
-        println!("{password}");
-    } else {
-        println!("NO")
-    }
-    Ok(())
-}
-
-fn split_parse_iter<T>(line: &str) -> impl Iterator<Item = Result<T, T::Err>> + '_
-where
-    T: FromStr,
-    T::Err: std::error::Error,
-{
-    line.split_whitespace().map(|entry| entry.parse::<T>())
+    problem::run::<TestCases<Keyboard>>()
 }
 
+#[derive(Scan)]
 struct Keyboard {
     password: String,
 }
 
-impl Keyboard {
-    pub fn new(password: String) -> Self {
-        Self { password }
-    }
-}
+impl TestCase for Keyboard {
+    type Input = Self;
+    type Output = OrNo<String>;
+    const COUNT: RangeInclusive<u32> = 1..=1_000;
 
-impl Problem<Option<String>> for Keyboard {
-    fn from_reader(_reader: impl BufRead) -> Result<Self> {
-        unimplemented!()
+    fn solve(input: Self) -> Result<OrNo<String>> {
+        debug_assert!((1..=200).contains(&input.password.len()));
+        input.solve().map(OrNo)
     }
+}
 
+impl Keyboard {
     fn solve(self) -> Result<Option<String>> {
         let mut password = self.password.bytes();
         let first_letter = password.next().ok_or("we assumed that |password| >= 1")?;
@@ -76,7 +39,12 @@ impl Problem<Option<String>> for Keyboard {
                 return Ok(None);
             }
         }
-        let unused_letters = assembler.used_letters.all_unset();
+        let unused_letters = assembler
+            .used_letters
+            .iter_unset()
+            .take_while(|&index| index < 26)
+            .map(index_letter)
+            .collect::<String>();
         let mut layout = assembler.into_layout();
         layout.push_str(&unused_letters);
         Ok(Some(layout))
@@ -86,7 +54,7 @@ impl Problem<Option<String>> for Keyboard {
 type Letter = u8;
 
 struct KeyboardAssembler {
-    used_letters: LetterSet,
+    used_letters: BitSet<1>,
     layout: VecDeque<Letter>,
     position: usize,
 }
@@ -99,11 +67,11 @@ enum AssemblyResult {
 impl KeyboardAssembler {
     fn new(first_letter: Letter) -> KeyboardAssembler {
         let mut builder = Self {
-            used_letters: LetterSet::new(),
+            used_letters: BitSet::new(),
             layout: VecDeque::with_capacity(26),
             position: 0,
         };
-        builder.used_letters.set(first_letter);
+        builder.used_letters.insert(letter_index(first_letter));
         builder.layout.push_back(first_letter);
         builder
     }
@@ -115,7 +83,7 @@ impl KeyboardAssembler {
     }
 
     fn insert(&mut self, letter: Letter) -> AssemblyResult {
-        if self.used_letters.is_set(letter) {
+        if self.used_letters.contains(letter_index(letter)) {
             self.insert_present(letter)
         } else {
             self.insert_new(letter)
@@ -126,12 +94,12 @@ impl KeyboardAssembler {
         if self.position == 0 {
             // we can insert a new letter at the beginning of the keyboard
             self.layout.push_front(letter);
-            self.used_letters.set(letter);
+            self.used_letters.insert(letter_index(letter));
             AssemblyResult::Possible
         } else if self.position + 1 == self.layout.len() {
             // we can insert a new letter at the end of the keyboard
             self.layout.push_back(letter);
-            self.used_letters.set(letter);
+            self.used_letters.insert(letter_index(letter));
             self.position += 1;
             AssemblyResult::Possible
         } else if self.position >= self.layout.len() {
@@ -182,40 +150,12 @@ impl KeyboardAssembler {
     }
 }
 
-struct LetterSet {
-    bits: u32,
+fn letter_index(letter: Letter) -> usize {
+    usize::from(letter - b'a')
 }
 
-impl LetterSet {
-    fn new() -> Self {
-        Self { bits: 0u32 }
-    }
-
-    fn set(&mut self, letter: Letter) {
-        let index = Self::letter_to_index(letter);
-        self.bits |= 1u32 << index;
-    }
-
-    fn is_set(&self, letter: Letter) -> bool {
-        let index = Self::letter_to_index(letter);
-        self.is_index_set(index)
-    }
-
-    fn is_index_set(&self, index: u8) -> bool {
-        ((self.bits >> index) & 1u32) == 1u32
-    }
-
-    fn all_unset(&self) -> String {
-        ('a'..='z')
-            .enumerate()
-            .filter(|(index, _)| !self.is_index_set(*index as u8))
-            .map(|(_, letter)| letter)
-            .collect()
-    }
-
-    fn letter_to_index(letter: Letter) -> u8 {
-        letter - b'a'
-    }
+fn index_letter(index: usize) -> char {
+    char::from(b'a' + index as u8)
 }
 
 #[cfg(test)]
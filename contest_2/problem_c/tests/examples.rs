@@ -0,0 +1,71 @@
+use checker::{Checker, Tokens};
+
+harness::test_examples!(check_layouts);
+
+/// Accepts any layouts typing the passwords by pressing adjacent keys, answering `NO` exactly when
+/// the expected output does.
+fn check_layouts(input: &str, expected: &str, actual: &str) -> Result<(), String> {
+    let mut input = Tokens::new(input);
+    let mut expected = Tokens::new(expected);
+    let mut actual = Tokens::new(actual);
+    let case_count = input.parse::<usize>()?;
+    for _ in 0..case_count {
+        let password = input.next_token()?;
+        let verdict = expected.next_token()?;
+        if actual.next_token()? != verdict {
+            return Err(actual.error(format_args!("expected `{verdict}`")));
+        }
+        if verdict == "YES" {
+            expected.next_token()?;
+            let layout = actual.next_token()?;
+            check_layout(password, layout).map_err(|error| actual.error(error))?;
+        }
+    }
+    actual.finish()
+}
+
+fn check_layout(password: &str, layout: &str) -> Result<(), String> {
+    let mut letters = layout.bytes().collect::<Vec<_>>();
+    letters.sort_unstable();
+    if letters != (b'a'..=b'z').collect::<Vec<_>>() {
+        return Err(format!("`{layout}` is not an ordering of the alphabet"));
+    }
+    // Every letter is on the layout, as checked above.
+    let position = |letter| {
+        layout
+            .bytes()
+            .position(|key| key == letter)
+            .unwrap_or_default()
+    };
+    for pair in password.as_bytes().windows(2) {
+        if position(pair[0]).abs_diff(position(pair[1])) != 1 {
+            return Err(format!(
+                "`{}` and `{}` are not adjacent in `{layout}`",
+                pair[0] as char, pair[1] as char
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_layouts() {
+    let input = "2\nababa\nabcda\n";
+    let expected = "YES\nbacdefghijklmnopqrstuvwxyz\nNO\n";
+    assert_eq!(
+        Ok(()),
+        check_layouts.check(input, expected, "YES\nzyxwvutsrqponmlkjihgfedcab\nNO\n")
+    );
+    assert_eq!(
+        Err("token 2: `a` and `b` are not adjacent in `acbdefghijklmnopqrstuvwxyz`".to_owned()),
+        check_layouts.check(input, expected, "YES\nacbdefghijklmnopqrstuvwxyz\nNO\n")
+    );
+    assert_eq!(
+        Err("token 2: `bacdefghijklmnopqrstuvwxy` is not an ordering of the alphabet".to_owned()),
+        check_layouts.check(input, expected, "YES\nbacdefghijklmnopqrstuvwxy\nNO\n")
+    );
+    assert_eq!(
+        Err("token 3: expected `NO`".to_owned()),
+        check_layouts.check(input, expected, "YES\nbacdefghijklmnopqrstuvwxyz\nYES\n")
+    );
+}
@@ -1,50 +1,248 @@
-use std::io::BufRead;
+use output::Words;
+use problem::Problem;
+use scanner::{LineScanner, Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error + 'static>;
 
-trait Problem<T>: Sized {
-    fn from_reader(reader: impl BufRead) -> Result<Self>;
+fn main() -> Result<()> {
+    problem::run::<NumberGames>()
+}
 
-    fn solve(self) -> Result<T>;
+struct NumberGames {
+    district_count: usize,
+    removed_count: usize,
+    roads: Vec<(usize, usize)>,
 }
 
-fn main() -> Result<()> {
-    Ok(())
+impl Scan for NumberGames {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        let (district_count, removed_count) = {
+            let mut line = scanner.next_line()?;
+            let district_count = line.next_ranged(2..=1_000_000)?;
+            let removed_count = line.next_ranged(1..district_count)?;
+            (district_count, removed_count)
+        };
+
+        let mut roads = Vec::with_capacity(district_count - 1);
+        for _ in 1..district_count {
+            let (first, second): (usize, usize) = scanner
+                .next_line()?
+                .next_tuple((1..=district_count, 1..=district_count))?;
+            debug_assert_ne!(first, second);
+            roads.push((first - 1, second - 1));
+        }
+
+        Ok(NumberGames {
+            district_count,
+            removed_count,
+            roads,
+        })
+    }
+}
+
+impl Problem for NumberGames {
+    type Input = Self;
+    type Output = Words<usize>;
+
+    fn solve(input: Self) -> Result<Words<usize>> {
+        Ok(Words(input.solve()?))
+    }
+}
+
+impl NumberGames {
+    /// Returns the removed districts in increasing order.
+    ///
+    /// District `i` is worth more than all lesser ones together, so the most valuable one, `n`, is
+    /// always kept and the others are taken greedily in decreasing order: a district is kept along
+    /// with the path joining it to the kept ones if the path fits in the remaining budget. The kept
+    /// districts form a subtree containing `n`, so the length of that path is the depth of the
+    /// district less the number of its kept ancestors, which a Fenwick tree over the preorder keeps
+    /// track of, each kept district adding one to the range of its subtree. Every district is kept
+    /// at most once, so the whole greedy takes `O(n log n)` instead of walking the paths anew.
+    fn solve(self) -> Result<Vec<usize>> {
+        let root = self.district_count - 1;
+        let tree = RootedTree::new(self.district_count, &self.roads, root)?;
+        let mut kept = vec![false; self.district_count];
+        let mut kept_ancestors = FenwickTree::new(self.district_count);
+        let mut budget = self.district_count - self.removed_count;
+
+        for district in (0..self.district_count).rev() {
+            if kept[district] {
+                continue;
+            }
+            let path_length =
+                tree.depths[district] + 1 - kept_ancestors.sum(tree.entries[district]);
+            if path_length > budget {
+                continue;
+            }
+            budget -= path_length;
+            let mut current = district;
+            while !kept[current] {
+                kept[current] = true;
+                kept_ancestors.add(tree.entries[current], 1);
+                kept_ancestors.add(tree.exits[current], -1);
+                current = tree.parents[current];
+            }
+        }
+        debug_assert_eq!(budget, 0);
+
+        let removed = (0..self.district_count)
+            .filter(|&district| !kept[district])
+            .map(|district| district + 1)
+            .collect();
+        Ok(removed)
+    }
+}
+
+/// Tree with the vertices numbered in preorder, the subtree of a vertex spanning the preorder
+/// positions `entries[vertex]..exits[vertex]`.
+struct RootedTree {
+    parents: Vec<usize>,
+    depths: Vec<usize>,
+    entries: Vec<usize>,
+    exits: Vec<usize>,
+}
+
+impl RootedTree {
+    fn new(vertex_count: usize, edges: &[(usize, usize)], root: usize) -> Result<Self> {
+        let mut offsets = vec![0; vertex_count + 1];
+        for &(first, second) in edges {
+            offsets[first + 1] += 1;
+            offsets[second + 1] += 1;
+        }
+        for vertex in 0..vertex_count {
+            offsets[vertex + 1] += offsets[vertex];
+        }
+        let mut neighbours = vec![0; offsets[vertex_count]];
+        let mut next = offsets.clone();
+        for &(first, second) in edges {
+            neighbours[next[first]] = second;
+            next[first] += 1;
+            neighbours[next[second]] = first;
+            next[second] += 1;
+        }
+
+        // Walks the tree depth first with an explicit stack, as the paths may be a million long.
+        let mut parents = vec![root; vertex_count];
+        let mut depths = vec![0; vertex_count];
+        let mut entries = vec![usize::MAX; vertex_count];
+        let mut preorder = Vec::with_capacity(vertex_count);
+        let mut stack = vec![root];
+        while let Some(vertex) = stack.pop() {
+            entries[vertex] = preorder.len();
+            preorder.push(vertex);
+            for &neighbour in &neighbours[offsets[vertex]..offsets[vertex + 1]] {
+                if neighbour == parents[vertex] {
+                    continue;
+                }
+                if entries[neighbour] != usize::MAX {
+                    return Err("The roads contain a cycle".into());
+                }
+                parents[neighbour] = vertex;
+                depths[neighbour] = depths[vertex] + 1;
+                stack.push(neighbour);
+            }
+        }
+        if preorder.len() != vertex_count {
+            return Err("The roads do not connect all districts".into());
+        }
+
+        let mut exits = entries.iter().map(|entry| entry + 1).collect::<Vec<_>>();
+        for &vertex in preorder.iter().skip(1).rev() {
+            let parent = parents[vertex];
+            exits[parent] = exits[parent].max(exits[vertex]);
+        }
+
+        Ok(Self {
+            parents,
+            depths,
+            entries,
+            exits,
+        })
+    }
+}
+
+/// Fenwick tree of point updates and prefix sums, used with differences to add to ranges.
+struct FenwickTree {
+    values: Vec<isize>,
+}
+
+impl FenwickTree {
+    fn new(length: usize) -> Self {
+        Self {
+            values: vec![0; length + 1],
+        }
+    }
+
+    /// Adds the value to the element at the index, ignoring indices past the end.
+    fn add(&mut self, index: usize, value: isize) {
+        let mut index = index + 1;
+        while index < self.values.len() {
+            self.values[index] += value;
+            index += index & index.wrapping_neg();
+        }
+    }
+
+    /// Returns the sum of the elements up to and including the index.
+    fn sum(&self, index: usize) -> usize {
+        let mut index = index + 1;
+        let mut sum = 0;
+        while index > 0 {
+            sum += self.values[index];
+            index -= index & index.wrapping_neg();
+        }
+        sum as usize
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use scanner::StringScanner;
+
     use super::*;
 
-    // counterexample
-    // 6 3
-    // 1 4
-    // 4 6
-    // 1 5
-    // 5 2
-    // 3 6
-    // out: 1 2 5
+    #[test]
+    fn test_example_1() -> Result<()> {
+        let input = "6 3\n2 1\n2 6\n4 2\n5 6\n2 3\n";
+        let problem = NumberGames::scan(&mut StringScanner::from(input))?;
+        assert_eq!(vec![1, 3, 4], problem.solve()?);
+        Ok(())
+    }
+
+    /// District 5 is too far from 6 to be kept, which must not stop the lesser ones from being kept.
     #[test]
     fn test_counterexample() -> Result<()> {
+        let input = "6 3\n1 4\n4 6\n1 5\n5 2\n3 6\n";
+        let problem = NumberGames::scan(&mut StringScanner::from(input))?;
+        assert_eq!(vec![1, 2, 5], problem.solve()?);
         Ok(())
     }
 
-    // TLE on n^2 algorithms
-    // 1000000 500000
-    // 1000000 1
-    // 1 2
-    // 2 3
-    // ...
-    // 499998 499999
-    // 499999 500000
-    // 499999 500001
-    // 499999 500002
-    // ...
-    // 499999 999999
-    // out: 500000 ... 999999
+    /// A handle of half a million districts ending in half a million bristles, each of which would
+    /// walk the whole handle if the paths were not remembered.
     #[test]
     fn test_broomstick() -> Result<()> {
+        let district_count = 1_000_000;
+        let mut roads = vec![(district_count - 1, 0)];
+        roads.extend((1..499_999).map(|district| (district - 1, district)));
+        roads.extend((500_000..district_count).map(|district| (499_998, district - 1)));
+        let problem = NumberGames {
+            district_count,
+            removed_count: 500_000,
+            roads,
+        };
+        assert_eq!((500_000..=999_999).collect::<Vec<_>>(), problem.solve()?);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_roads() {
+        let cycle = NumberGames {
+            district_count: 4,
+            removed_count: 1,
+            roads: vec![(0, 1), (1, 2), (2, 0)],
+        };
+        assert!(cycle.solve().is_err());
+    }
 }
@@ -1,25 +1,16 @@
 use std::collections::HashSet;
-use std::io;
-use std::io::BufRead;
-use std::str::FromStr;
+
+use problem::Problem;
+use scanner::{LineScanner, Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error + 'static>;
 
-trait Problem<T>: Sized {
-    fn from_reader(reader: impl BufRead) -> Result<Self>;
-
-    fn solve(self) -> Result<T>;
-}
-
 fn main() -> Result<()> {
-    let stdin = io::stdin().lock();
-    let problem = Orchestra::from_reader(stdin)?;
-    let result = problem.solve()?;
-    println!("{result}");
-    Ok(())
+    problem::run::<Orchestra>()
 }
 
+#[derive(Clone, Debug)]
 struct Orchestra {
     rows: u8,
     columns: u8,
@@ -27,45 +18,23 @@ struct Orchestra {
     violas: HashSet<(u8, u8)>,
 }
 
-impl Problem<u32> for Orchestra {
-    fn from_reader(reader: impl BufRead) -> Result<Self> {
-        fn split_parse_iter<T>(line: &str) -> impl Iterator<Item = Result<T, T::Err>> + '_
-        where
-            T: FromStr,
-            T::Err: std::error::Error,
-        {
-            line.split_whitespace().map(|entry| entry.parse::<T>())
-        }
-
-        let mut lines = reader.lines();
-
-        let first_line = lines.next().ok_or("Missing first line")??;
-        let mut first_line = split_parse_iter::<u8>(&first_line);
-        let rows = first_line.next().ok_or("Missing rows")??;
-        let columns = first_line.next().ok_or("Missing columns")??;
-        let viola_count = first_line.next().ok_or("Missing viola count")??;
-        let minimum_viola_count = first_line.next().ok_or("Missing minimum viola count")??;
-
-        debug_assert!((1..=10).contains(&rows));
-        debug_assert!((1..=10).contains(&columns));
-        debug_assert!((1..=10).contains(&viola_count));
-        debug_assert!((1..=viola_count).contains(&minimum_viola_count));
-        debug_assert!(first_line.next().is_none());
+impl Scan for Orchestra {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        let (rows, columns, viola_count, minimum_viola_count) = {
+            let mut line = scanner.next_line()?;
+            let rows = line.next_ranged(1..=10)?;
+            let columns = line.next_ranged(1..=10)?;
+            let viola_count = line.next_ranged(1..=10)?;
+            let minimum_viola_count = line.next_ranged(1..=viola_count)?;
+            (rows, columns, viola_count, minimum_viola_count)
+        };
 
         let mut violas = HashSet::with_capacity(viola_count as usize);
-        for line in lines {
-            let line = line?;
-            let mut line = split_parse_iter::<u8>(&line);
-            let x = line.next().ok_or("Missing x")??;
-            let y = line.next().ok_or("Missing y")??;
+        for _ in 0..viola_count {
+            let (x, y): (u8, u8) = scanner.next_line()?.next_tuple((1..=rows, 1..=columns))?;
             violas.insert((x - 1, y - 1));
         }
-
         debug_assert_eq!(violas.len(), viola_count as usize);
-        for (x, y) in &violas {
-            debug_assert!((0..=rows - 1).contains(x));
-            debug_assert!((0..=columns - 1).contains(y));
-        }
 
         Ok(Orchestra {
             rows,
@@ -74,8 +43,55 @@ impl Problem<u32> for Orchestra {
             violas,
         })
     }
+}
+
+impl Problem for Orchestra {
+    type Input = Self;
+    type Output = u32;
+
+    fn solve(input: Self) -> Result<u32> {
+        input.solve()
+    }
+}
 
+impl Orchestra {
+    /// Counts the rectangles with enough violas, finding the number of violas in each from sums of
+    /// the prefix rectangles.
     fn solve(self) -> Result<u32> {
+        let (rows, columns) = (self.rows as usize, self.columns as usize);
+        // Violas in the rows before `row` and the columns before `column`.
+        let mut prefix_violas = vec![vec![0u8; columns + 1]; rows + 1];
+        for row in 0..rows {
+            for column in 0..columns {
+                let viola = u8::from(self.violas.contains(&(row as u8, column as u8)));
+                prefix_violas[row + 1][column + 1] = prefix_violas[row][column + 1]
+                    + prefix_violas[row + 1][column]
+                    - prefix_violas[row][column]
+                    + viola;
+            }
+        }
+
+        let mut photo_count = 0;
+        for top in 0..rows {
+            for bottom in top + 1..=rows {
+                for left in 0..columns {
+                    for right in left + 1..=columns {
+                        let viola_count = prefix_violas[bottom][right] + prefix_violas[top][left]
+                            - prefix_violas[top][right]
+                            - prefix_violas[bottom][left];
+                        if viola_count >= self.minimum_viola_count {
+                            photo_count += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(photo_count)
+    }
+
+    #[cfg(test)]
+    fn solve_by_brute_force(self) -> u32 {
         let mut photo_count = 0;
 
         for row in 0..self.rows {
@@ -93,11 +109,10 @@ impl Problem<u32> for Orchestra {
             }
         }
 
-        Ok(photo_count)
+        photo_count
     }
-}
 
-impl Orchestra {
+    #[cfg(test)]
     fn count_violas_in_rectangle(&self, column: u8, row: u8, width: u8, height: u8) -> u8 {
         let mut viola_count = 0;
         for x in column..column + width {
@@ -113,6 +128,8 @@ impl Orchestra {
 
 #[cfg(test)]
 mod tests {
+    use stress::{Random, Stress};
+
     use super::*;
 
     #[test]
@@ -153,4 +170,46 @@ mod tests {
         assert_eq!(4, actual);
         Ok(())
     }
+
+    impl Stress for Orchestra {
+        type Input = Self;
+        type Output = u32;
+        const MAX_SIZE: usize = 10;
+
+        fn generate(random: &mut Random, size: usize) -> Self {
+            let rows = random.range(1..=size as u64) as u8;
+            let columns = random.range(1..=size as u64) as u8;
+            let cells = u64::from(rows) * u64::from(columns);
+            let viola_count = random.range(1..=cells.min(10)) as usize;
+            let violas = random
+                .grid_points(
+                    viola_count,
+                    0..=i64::from(rows) - 1,
+                    0..=i64::from(columns) - 1,
+                )
+                .into_iter()
+                .map(|(row, column)| (row as u8, column as u8))
+                .collect();
+            let minimum_viola_count = random.range(1..=viola_count as u64) as u8;
+            Orchestra {
+                rows,
+                columns,
+                minimum_viola_count,
+                violas,
+            }
+        }
+
+        fn reference(input: &Self) -> u32 {
+            input.clone().solve_by_brute_force()
+        }
+
+        fn solve(input: &Self) -> u32 {
+            input.clone().solve().expect("the input is valid")
+        }
+    }
+
+    #[test]
+    fn test_stress() {
+        stress::check::<Orchestra>(2000, 0);
+    }
 }
@@ -1,39 +1,13 @@
-use std::io;
-use std::io::{BufRead, Lines};
-
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use modular::DynModInt;
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let problem = Palindromes::from_lines(lines)?;
-    let result = problem.solve()?;
-    println!("{result}");
-    Ok(())
+    problem::run::<Palindromes>()
 }
 
 struct Palindromes {
@@ -41,9 +15,9 @@ struct Palindromes {
     modulus: u64,
 }
 
-impl Palindromes {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        parse_next! { lines =>
+impl Scan for Palindromes {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next! { scanner =>
             let palindromes_count: u32 = 1..=10u32.pow(5);
             let modulus: u64 = 1..=10u64.pow(9)
         }
@@ -53,17 +27,27 @@ impl Palindromes {
             modulus,
         })
     }
+}
 
+impl Problem for Palindromes {
+    type Input = Self;
+    type Output = u32;
+
+    fn solve(input: Self) -> Result<u32> {
+        input.solve()
+    }
+}
+
+impl Palindromes {
     fn solve(self) -> Result<u32> {
-        let mut sum = 0;
+        let mut sum = DynModInt::new(0, self.modulus);
         for number in 1..=self.palindromes_count {
             let number_string = number.to_string();
             let number_reversed = number_string.chars().rev().collect::<String>();
             let palindrome = format!("{number_string}{number_reversed}");
-            let palindrome = palindrome.parse::<u64>()? % self.modulus;
-            sum = (sum + palindrome) % self.modulus;
+            sum += DynModInt::new(palindrome.parse()?, self.modulus);
         }
-        Ok(sum as u32)
+        Ok(sum.value() as u32)
     }
 }
 
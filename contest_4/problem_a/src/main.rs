@@ -1,79 +1,39 @@
 use std::cmp::Ordering;
-use std::io;
-use std::io::BufRead;
 use std::vec::IntoIter;
 
-macro_rules! parse_next {
-    ($lines:ident => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:ident => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use output::Lines;
+use problem::Problem;
+use scanner::Scan;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
-trait Problem
-where
-    Self: Sized,
-{
-    type Output;
-
-    fn from_reader(reader: impl BufRead) -> Result<Self>;
-
-    fn solve(self) -> Result<Self::Output>;
-}
-
 fn main() -> Result<()> {
-    let stdin = io::stdin().lock();
-    let problem = Siege::from_reader(stdin)?;
-    let result = problem.solve()?;
-    for standing_warriors in result {
-        println!("{standing_warriors}")
-    }
-    Ok(())
+    problem::run::<Siege>()
 }
 
+#[derive(Scan)]
 struct Siege {
+    #[scan(range = 1..=200_000)]
+    warrior_count: u32,
+    #[scan(same_line, range = 1..=200_000)]
+    battle_time: u32,
+    #[scan(len = warrior_count, range = 1..=10u32.pow(9))]
     warriors_strengths: Vec<u32>,
+    #[scan(len = battle_time, range = 1..=10u64.pow(14))]
     arrows: Vec<u64>,
 }
 
 impl Problem for Siege {
-    type Output = StandingWarriors;
-
-    fn from_reader(reader: impl BufRead) -> Result<Self> {
-        let mut lines = reader.lines();
-        parse_next! { lines =>
-            let warrior_count: u32 = 1..=200_000;
-            let battle_time: u32 = 1..=200_000
-        }
-        parse_next!(lines => let warriors_strengths: Vec<_> = [1..=10u32.pow(9); warrior_count]);
-        parse_next!(lines => let arrows: Vec<_> = [1..=10u64.pow(14); battle_time]);
-        debug_assert!(lines.next().is_none());
-
-        Ok(Siege {
-            warriors_strengths,
-            arrows,
-        })
+    type Input = Self;
+    type Output = Lines<u32>;
+
+    fn solve(input: Self) -> Result<Lines<u32>> {
+        Ok(Lines(input.solve()?.collect()))
     }
+}
 
+impl Siege {
     fn solve(self) -> Result<StandingWarriors> {
         let strength_prefix_sums = self
             .warriors_strengths
@@ -140,11 +100,15 @@ impl StandingWarriors {
 
 #[cfg(test)]
 mod tests {
+    use generator::{Generator, Input, Random, Size};
+
     use super::*;
 
     #[test]
     fn test_example_1() -> Result<()> {
         let problem = Siege {
+            warrior_count: 5,
+            battle_time: 5,
             warriors_strengths: vec![1, 2, 1, 2, 1],
             arrows: vec![3, 10, 1, 1, 1],
         };
@@ -156,6 +120,8 @@ mod tests {
     #[test]
     fn test_example_2() -> Result<()> {
         let problem = Siege {
+            warrior_count: 4,
+            battle_time: 4,
             warriors_strengths: vec![1, 2, 3, 4],
             arrows: vec![9, 1, 10, 6],
         };
@@ -163,4 +129,45 @@ mod tests {
         assert_eq!(vec![1, 4, 4, 1], actual);
         Ok(())
     }
+
+    fn siege(strengths: Vec<u32>, arrows: Vec<u64>) -> String {
+        Input::new()
+            .line([strengths.len(), arrows.len()])
+            .line(strengths)
+            .line(arrows)
+            .into()
+    }
+
+    fn sized_siege(random: &mut Random, size: Size) -> String {
+        let warrior_count = size.draw(random, 1..=size.pick(10, 200_000));
+        let battle_time = size.draw(random, 1..=size.pick(10, 200_000));
+        siege(
+            random.array(warrior_count, 1..=size.pick(10, 10u32.pow(9))),
+            random.array(battle_time, 1..=size.pick(30, 10u64.pow(14))),
+        )
+    }
+
+    /// Every minute the arrows kill exactly all warriors, who then stand up again.
+    fn revivals(random: &mut Random) -> String {
+        let strengths = random.array(200_000, 1..=5 * 10u32.pow(8));
+        let total = strengths.iter().map(|&strength| u64::from(strength)).sum();
+        siege(strengths, vec![total; 200_000])
+    }
+
+    /// Single arrows, killing the strongest warriors one at a time over the whole battle.
+    fn single_arrows(_random: &mut Random) -> String {
+        siege(vec![1; 200_000], vec![1; 200_000])
+    }
+
+    const GENERATORS: [Generator; 4] = [
+        Generator::sized("random", sized_siege, Size::Small),
+        Generator::sized("maximal", sized_siege, Size::Maximal),
+        Generator::new("revivals", revivals),
+        Generator::new("single_arrows", single_arrows),
+    ];
+
+    #[test]
+    fn test_generators() -> Result<()> {
+        generator::check::<Siege>(&GENERATORS, 0..10)
+    }
 }
@@ -1,64 +1,36 @@
-use std::io;
-use std::io::{BufRead, Lines};
+use std::ops::RangeInclusive;
 
-macro_rules! parse_next {
-    ($lines:ident => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:ident => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use problem::{TestCase, TestCases};
+use scanner::Scan;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin().lock();
-    let mut lines = stdin.lines();
-    parse_next!(lines => let test_cases: u32 = 1..=2 * 10u32.pow(5));
-
-    for _ in 0..test_cases {
-        let problem = DeliveryDilemma::from_lines(&mut lines)?;
-        let result = problem.solve()?;
-        println!("{result}");
-    }
-
-    debug_assert!(lines.next().is_none());
-    Ok(())
+    problem::run::<TestCases<DeliveryDilemma>>()
 }
 
+#[derive(Scan)]
 struct DeliveryDilemma {
+    #[scan(range = 1..=2 * 10u32.pow(5))]
+    dish_count: u32,
+    #[scan(len = dish_count, range = 1..=10u32.pow(9))]
     delivery_times: Vec<u32>,
+    #[scan(len = dish_count, range = 1..=10u32.pow(9))]
     pick_up_times: Vec<u32>,
 }
 
-impl DeliveryDilemma {
-    fn from_lines(lines: &mut Lines<impl BufRead>) -> Result<Self> {
-        parse_next!(lines => let dish_count: u32 = 1..=2 * 10u32.pow(5));
-        parse_next!(lines => let delivery_times: Vec<_> = [1..=10u32.pow(9); dish_count]);
-        parse_next!(lines => let pick_up_times: Vec<_> = [1..=10u32.pow(9); dish_count]);
+impl TestCase for DeliveryDilemma {
+    type Input = Self;
+    type Output = u64;
+    const COUNT: RangeInclusive<u32> = 1..=2 * 10u32.pow(5);
 
-        Ok(DeliveryDilemma {
-            delivery_times,
-            pick_up_times,
-        })
+    fn solve(input: Self) -> Result<u64> {
+        input.solve()
     }
+}
 
+impl DeliveryDilemma {
     fn solve(self) -> Result<u64> {
         let pick_up_times_sum = self
             .pick_up_times
@@ -96,6 +68,7 @@ mod tests {
     #[test]
     fn test_example_1() -> Result<()> {
         let problem = DeliveryDilemma {
+            dish_count: 4,
             delivery_times: vec![3, 7, 4, 5],
             pick_up_times: vec![2, 1, 2, 4],
         };
@@ -107,6 +80,7 @@ mod tests {
     #[test]
     fn test_example_2() -> Result<()> {
         let problem = DeliveryDilemma {
+            dish_count: 4,
             delivery_times: vec![1, 2, 3, 4],
             pick_up_times: vec![3, 3, 3, 3],
         };
@@ -118,6 +92,7 @@ mod tests {
     #[test]
     fn test_example_3() -> Result<()> {
         let problem = DeliveryDilemma {
+            dish_count: 2,
             delivery_times: vec![1, 2],
             pick_up_times: vec![10, 10],
         };
@@ -129,6 +104,7 @@ mod tests {
     #[test]
     fn test_example_4() -> Result<()> {
         let problem = DeliveryDilemma {
+            dish_count: 2,
             delivery_times: vec![10, 10],
             pick_up_times: vec![1, 2],
         };
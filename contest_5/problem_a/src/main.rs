@@ -1,69 +1,57 @@
+use std::cmp;
 use std::collections::HashMap;
-use std::io::{BufRead, Lines};
-use std::{cmp, io};
 
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use problem::Problem;
+use scanner::Scan;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let message = Message::from_lines(lines)?;
-    let result = message.solve()?;
-    println!("{result}");
-    Ok(())
+    problem::run::<Message>()
 }
 
+#[derive(Scan)]
 struct Message {
+    #[scan(range = 1..=10u32.pow(5))]
+    word_count: u32,
+    #[scan(same_line, range = 1..=word_count)]
     group_count: u32,
-    words: String,
+    #[scan(same_line, range = 1..=10u32.pow(5))]
+    message_word_count: u32,
+    #[scan(len = word_count)]
+    words: Vec<String>,
+    #[scan(len = word_count, range = 1..=10u32.pow(9))]
     words_costs: Vec<u32>,
-    words_groups: Vec<usize>,
-    message: String,
+    #[scan(nested, len = group_count)]
+    groups: Vec<Group>,
+    #[scan(len = message_word_count)]
+    message: Vec<String>,
 }
 
-impl Message {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        parse_next! { lines =>
-            let word_count: u32 = 1..=10u32.pow(5);
-            let group_count: u32 = 1..=word_count;
-            let message_word_count: u32 = 1..=10u32.pow(5)
-        }
-        let words = lines.next().ok_or("Missing words line")??;
-        parse_next!(lines => let words_costs: Vec<_> = [1..=10u32.pow(9); word_count]);
+#[derive(Scan)]
+struct Group {
+    #[scan(range = 1..=10u32.pow(5))]
+    length: u32,
+    #[scan(same_line, len = length, range = 1..=10u32.pow(5))]
+    members: Vec<u32>,
+}
+
+impl Problem for Message {
+    type Input = Self;
+    type Output = u64;
 
-        let mut words_groups = vec![None; word_count as usize];
-        for group_index in 0..group_count as usize {
-            let line = lines.next().ok_or("Missing next line")??;
-            let mut line = line.split_whitespace().map(|entry| entry.parse::<u32>());
-            let _group_length = line.next().ok_or("Missing group length")??;
+    fn solve(input: Self) -> Result<u64> {
+        input.solve()
+    }
+}
 
-            for group_member in line {
-                let group_id = group_member?;
-                debug_assert!((1..=word_count).contains(&group_id));
+impl Message {
+    fn words_groups(&self) -> Result<Vec<usize>> {
+        let mut words_groups = vec![None; self.word_count as usize];
+        for (group_index, group) in self.groups.iter().enumerate() {
+            for &group_id in &group.members {
+                debug_assert!((1..=self.word_count).contains(&group_id));
                 words_groups[group_id as usize - 1] = Some(group_index + 1);
             }
         }
@@ -71,21 +59,13 @@ impl Message {
             .into_iter()
             .collect::<Option<Vec<usize>>>()
             .ok_or("Missing group id for some word")?;
-
-        let message = lines.next().ok_or("Missing message line")??;
-
-        Ok(Message {
-            group_count,
-            words,
-            words_costs,
-            words_groups,
-            message,
-        })
+        Ok(words_groups)
     }
 
     fn solve(self) -> Result<u64> {
+        let words_groups = self.words_groups()?;
         let mut groups_costs: Vec<Option<u32>> = vec![None; self.group_count as usize];
-        for (word_index, group_id) in self.words_groups.iter().enumerate() {
+        for (word_index, group_id) in words_groups.iter().enumerate() {
             let word_cost = self.words_costs[word_index];
             let group_cost = &mut groups_costs[group_id - 1];
             let cost = group_cost.map_or(word_cost, |cost| cmp::min(cost, word_cost));
@@ -98,15 +78,15 @@ impl Message {
 
         let words_to_id = self
             .words
-            .split_whitespace()
+            .iter()
             .enumerate()
             .map(|(index, word)| (word, index + 1))
             .collect::<HashMap<_, _>>();
         let total_cost = self
             .message
-            .split_whitespace()
+            .iter()
             .map(|word| words_to_id[word])
-            .map(|word_id| self.words_groups[word_id - 1])
+            .map(|word_id| words_groups[word_id - 1])
             .map(|group_id| groups_costs[group_id - 1] as u64)
             .sum();
         Ok(total_cost)
@@ -117,14 +97,27 @@ impl Message {
 mod tests {
     use super::*;
 
+    fn to_words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_owned).collect()
+    }
+
+    fn group(members: &[u32]) -> Group {
+        Group {
+            length: members.len() as u32,
+            members: members.to_vec(),
+        }
+    }
+
     #[test]
     fn test_example_1() -> Result<()> {
         let problem = Message {
+            word_count: 5,
             group_count: 4,
-            words: "i loser am the second".to_owned(),
+            message_word_count: 4,
+            words: to_words("i loser am the second"),
             words_costs: vec![100, 1, 1, 5, 10],
-            words_groups: vec![1, 3, 2, 4, 3],
-            message: "i am the second".to_owned(),
+            groups: vec![group(&[1]), group(&[3]), group(&[2, 5]), group(&[4])],
+            message: to_words("i am the second"),
         };
         let actual = problem.solve()?;
         assert_eq!(107, actual);
@@ -134,11 +127,13 @@ mod tests {
     #[test]
     fn test_example_2() -> Result<()> {
         let problem = Message {
+            word_count: 5,
             group_count: 4,
-            words: "i loser am the second".to_owned(),
+            message_word_count: 4,
+            words: to_words("i loser am the second"),
             words_costs: vec![100, 20, 1, 5, 10],
-            words_groups: vec![1, 3, 2, 4, 3],
-            message: "i am the second".to_owned(),
+            groups: vec![group(&[1]), group(&[3]), group(&[2, 5]), group(&[4])],
+            message: to_words("i am the second"),
         };
         let actual = problem.solve()?;
         assert_eq!(116, actual);
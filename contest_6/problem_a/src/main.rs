@@ -1,63 +1,35 @@
-use std::io;
-use std::io::{BufRead, Lines};
-
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use problem::Problem;
+use scanner::Scan;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let message = LectureSleep::from_lines(lines)?;
-    let result = message.solve()?;
-    println!("{result}");
-    Ok(())
+    problem::run::<LectureSleep>()
 }
 
+#[derive(Scan)]
 struct LectureSleep {
+    #[scan(range = 1..=10usize.pow(5))]
+    lecture_duration: usize,
+    #[scan(same_line, range = 1..=lecture_duration)]
     awake_duration: usize,
+    #[scan(len = lecture_duration, range = 1..=10u32.pow(4))]
     theorems: Vec<u32>,
+    #[scan(len = lecture_duration, range = 0..=1)]
     behaviour: Vec<u8>,
 }
 
-impl LectureSleep {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        parse_next! { lines =>
-            let lecture_duration: usize = 1..=10usize.pow(5);
-            let awake_duration: usize = 1..=lecture_duration
-        }
-        parse_next!(lines => let theorems: Vec<_> = [1..=10u32.pow(4); lecture_duration]);
-        parse_next!(lines => let behaviour: Vec<u8> = [0..=1; lecture_duration]);
+impl Problem for LectureSleep {
+    type Input = Self;
+    type Output = u32;
 
-        Ok(LectureSleep {
-            awake_duration,
-            theorems,
-            behaviour,
-        })
+    fn solve(input: Self) -> Result<u32> {
+        input.solve()
     }
+}
 
+impl LectureSleep {
     fn solve(self) -> Result<u32> {
         let technique_start_index = self
             .theorems
@@ -97,6 +69,7 @@ mod tests {
     #[test]
     fn test_example() -> Result<()> {
         let problem = LectureSleep {
+            lecture_duration: 6,
             awake_duration: 3,
             theorems: vec![1, 3, 5, 2, 5, 4],
             behaviour: vec![1, 1, 0, 1, 0, 0],
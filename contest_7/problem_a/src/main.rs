@@ -1,69 +1,68 @@
-extern crate core;
-
-use std::io;
-use std::io::{BufRead, Lines};
-
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let problem = MultiplicationTable::from_lines(lines)?;
-    let result = problem.solve()?;
-    println!("{result}");
-    Ok(())
+    problem::run::<MultiplicationTable>()
 }
 
+#[derive(Clone, Debug)]
 struct MultiplicationTable {
     size: u32,
     number: u32,
 }
 
-impl MultiplicationTable {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        parse_next! { lines =>
+impl Scan for MultiplicationTable {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next! { scanner =>
             let size: u32 = 1..=10u32.pow(5);
             let number: u32 = 1..=10u32.pow(9)
         }
 
         Ok(MultiplicationTable { size, number })
     }
+}
+
+impl Problem for MultiplicationTable {
+    type Input = Self;
+    type Output = usize;
+
+    fn solve(input: Self) -> Result<usize> {
+        input.solve()
+    }
+}
 
+impl MultiplicationTable {
+    /// Counts the cells `d * (number / d)` of the table, for the divisors `d` of the number
+    /// enumerated from its factorization.
     fn solve(self) -> Result<usize> {
-        let solutions = (1..=self.size)
-            .filter(|divisor| self.number % divisor == 0 && self.number / divisor <= self.size)
+        let number = u64::from(self.number);
+        let size = u64::from(self.size);
+        let factorization = number_theory::factorize(number);
+        let solutions = number_theory::divisors(&factorization)
+            .into_iter()
+            .filter(|divisor| *divisor <= size && number / divisor <= size)
             .count();
 
         Ok(solutions)
     }
+
+    /// Counts the cells like [`Self::solve`], trying every row of the table.
+    #[cfg(test)]
+    fn solve_by_enumeration(self) -> usize {
+        (1..=self.size)
+            .filter(|divisor| self.number % divisor == 0 && self.number / divisor <= self.size)
+            .count()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use stress::{Random, Stress};
+
     use super::*;
 
     #[test]
@@ -98,4 +97,47 @@ mod tests {
         assert_eq!(0, actual);
         Ok(())
     }
+
+    #[test]
+    fn test_highly_composite() -> Result<()> {
+        let problem = MultiplicationTable {
+            size: 10u32.pow(5),
+            number: 735_134_400,
+        };
+        let expected = problem.clone().solve_by_enumeration();
+        let actual = problem.solve()?;
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    impl Stress for MultiplicationTable {
+        type Input = Self;
+        type Output = usize;
+        const MAX_SIZE: usize = 100;
+
+        /// Generates tables of up to `1000 * size` rows, with the number in the table half of the
+        /// time.
+        fn generate(random: &mut Random, size: usize) -> Self {
+            let size = random.range(1..=1000 * size as u32);
+            let number = if random.chance(0.5) {
+                random.range(1..=size) * random.range(1..=size).min(10u32.pow(9) / size)
+            } else {
+                random.range(1..=10u32.pow(9))
+            };
+            MultiplicationTable { size, number }
+        }
+
+        fn reference(input: &Self) -> usize {
+            input.clone().solve_by_enumeration()
+        }
+
+        fn solve(input: &Self) -> usize {
+            input.clone().solve().expect("the input is valid")
+        }
+    }
+
+    #[test]
+    fn test_stress() {
+        stress::check::<MultiplicationTable>(2000, 0);
+    }
 }
@@ -1,29 +1,14 @@
-use std::io;
-use std::io::{BufRead, Lines};
-
-macro_rules! parse_next {
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use modular::combinatorics;
+use output::Words;
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let problem = Teams::from_lines(lines)?;
-    let (minimum, maximum) = problem.solve()?;
-    println!("{minimum} {maximum}");
-    Ok(())
+    problem::run::<Teams>()
 }
 
 struct Teams {
@@ -31,9 +16,9 @@ struct Teams {
     teams: u64,
 }
 
-impl Teams {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        parse_next! { lines =>
+impl Scan for Teams {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next! { scanner =>
             let participants: u64 = 1..=10u64.pow(9);
             let teams: u64 = 1..=10u64.pow(9)
         }
@@ -43,25 +28,37 @@ impl Teams {
             teams,
         })
     }
+}
+
+impl Problem for Teams {
+    type Input = Self;
+    type Output = Words<u64>;
 
+    fn solve(input: Self) -> Result<Words<u64>> {
+        let (minimum, maximum) = input.solve()?;
+        Ok(Words(vec![minimum, maximum]))
+    }
+}
+
+impl Teams {
     fn solve(self) -> Result<(u64, u64)> {
-        Ok((self.minimum(), self.maximum()))
+        Ok((self.minimum()?, self.maximum()?))
     }
 
-    fn minimum(&self) -> u64 {
+    fn minimum(&self) -> Result<u64> {
         let members = self.participants / self.teams;
         let one_more_member = self.participants % self.teams;
-        let pairs = Self::number_of_pairs(members);
-        (self.teams - one_more_member) * pairs + one_more_member * (pairs + members)
+        let pairs = Self::number_of_pairs(members)?;
+        Ok((self.teams - one_more_member) * pairs + one_more_member * (pairs + members))
     }
 
-    fn maximum(&self) -> u64 {
+    fn maximum(&self) -> Result<u64> {
         Self::number_of_pairs(self.participants - (self.teams - 1))
     }
 
-    fn number_of_pairs(members: u64) -> u64 {
-        let n = members - 1;
-        n * (n + 1) / 2
+    fn number_of_pairs(members: u64) -> Result<u64> {
+        let pairs = combinatorics::binomial(members, 2).ok_or("Too many pairs")?;
+        Ok(u64::try_from(pairs)?)
     }
 }
 
@@ -1,54 +1,51 @@
-use std::io;
-use std::io::{BufRead, Lines};
-
-macro_rules! parse_next {
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+use modular::combinatorics;
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let problem = Benches::from_lines(lines)?;
-    let result = problem.solve()?;
-    println!("{result}");
-    Ok(())
+    problem::run::<Benches>()
 }
 
 struct Benches {
     paths: u64,
+    benches: u64,
 }
 
-impl Benches {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        parse_next!(lines => let paths: u64 = 5..=100);
-        Ok(Benches { paths })
+impl Scan for Benches {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next!(scanner => let paths: u64 = 5..=100);
+        Ok(Benches { paths, benches: 5 })
     }
+}
 
+impl Problem for Benches {
+    type Input = Self;
+    type Output = u64;
+
+    fn solve(input: Self) -> Result<u64> {
+        input.solve()
+    }
+}
+
+impl Benches {
     /// The result is (p choose b)^2 * b!, where p is the number of paths and b is the number of
     /// benches.
     /// At first, we choose in what columns and rows we'll be putting benches using binomial
     /// coefficient. Then on those 2-dimensional bxb sub-arrays we count all possible bench
     /// arrangements using factorial.
     fn solve(self) -> Result<u64> {
-        const BENCHES: u64 = 5;
-        const BENCHES_FACTORIAL: u64 = 2 * 3 * 4 * 5;
-        let result = ((self.paths - BENCHES + 1)..=self.paths)
-            .into_iter()
-            .product::<u64>();
-        let result = (result / BENCHES_FACTORIAL) * result;
-        Ok(result)
+        let overflow = "The number of arrangements does not fit in u64";
+        let lines = combinatorics::binomial(self.paths, self.benches).ok_or(overflow)?;
+        let arrangements = combinatorics::factorial(self.benches).ok_or(overflow)?;
+        let result = lines
+            .checked_mul(lines)
+            .and_then(|squared| squared.checked_mul(arrangements))
+            .ok_or(overflow)?;
+        Ok(u64::try_from(result)?)
     }
 }
 
@@ -58,9 +55,37 @@ mod tests {
 
     #[test]
     fn test_example() -> Result<()> {
-        let problem = Benches { paths: 5 };
+        let problem = Benches {
+            paths: 5,
+            benches: 5,
+        };
         let actual = problem.solve()?;
         assert_eq!(120, actual);
         Ok(())
     }
+
+    #[test]
+    fn test_bench_counts() -> Result<()> {
+        let problem = Benches {
+            paths: 100,
+            benches: 5,
+        };
+        assert_eq!(680_185_280_130_048_000, problem.solve()?);
+        let problem = Benches {
+            paths: 4,
+            benches: 2,
+        };
+        assert_eq!(6 * 6 * 2, problem.solve()?);
+        let problem = Benches {
+            paths: 3,
+            benches: 4,
+        };
+        assert_eq!(0, problem.solve()?);
+        let problem = Benches {
+            paths: 100,
+            benches: 20,
+        };
+        assert!(problem.solve().is_err());
+        Ok(())
+    }
 }
@@ -1,56 +1,80 @@
+use output::Lines;
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 extern crate core;
 
-use std::io::{BufRead, Lines};
-use std::{io, vec};
-
-macro_rules! parse_next {
-    ($lines:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let line = line.split_whitespace().map(|entry| entry.parse::<_>());
-        let $name = line.collect::<Result<$ty, _>>()?;
-
-        debug_assert_eq!($name.len(), $count as usize);
-        for item in &$name {
-            debug_assert!(($range).contains(item));
-        }
-    };
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
-
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let lines = stdin.lines();
-    let problem = Badge::from_lines(lines)?;
-    let result = problem.solve()?;
-    result.into_iter().for_each(|result| println!("{result}"));
-    Ok(())
+    problem::run::<Badge>()
 }
 
+#[derive(Clone, Debug)]
 struct Badge {
     reported_by: Vec<usize>,
 }
 
-impl Badge {
-    fn from_lines(mut lines: Lines<impl BufRead>) -> Result<Self> {
-        parse_next!(lines => let size: usize = 1..=1000);
-        parse_next!(lines => let reported_by: Vec<usize> = [1..=size; size]);
+impl Scan for Badge {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next!(scanner => let size: usize = 1..=1000);
+        parse_next!(scanner => let reported_by: Vec<usize> = [1..=size; size]);
         Ok(Badge { reported_by })
     }
+}
+
+impl Problem for Badge {
+    type Input = Self;
+    type Output = Lines<usize>;
 
+    fn solve(input: Self) -> Result<Lines<usize>> {
+        input.solve().map(Lines)
+    }
+}
+
+impl Badge {
+    /// Finds the student who gets the second hole for every start. It is where the path from the
+    /// start enters a cycle, so students on cycles get it themselves and every other student gets
+    /// the answer of the student they reported.
     fn solve(self) -> Result<Vec<usize>> {
-        let solutions = (1..=self.reported_by.len())
+        let size = self.reported_by.len();
+        let reported = |student: usize| self.reported_by[student] - 1;
+        let mut answers = vec![None; size];
+        let mut visited = vec![false; size];
+        for start in 0..size {
+            let mut path = Vec::new();
+            let mut student = start;
+            while answers[student].is_none() && !visited[student] {
+                visited[student] = true;
+                path.push(student);
+                student = reported(student);
+            }
+            if answers[student].is_none() {
+                let cycle_start = path
+                    .iter()
+                    .position(|&visited| visited == student)
+                    .ok_or("an unanswered walk ends on its own path")?;
+                for &member in &path[cycle_start..] {
+                    answers[member] = Some(member);
+                }
+                path.truncate(cycle_start);
+            }
+            for &student in path.iter().rev() {
+                answers[student] = answers[reported(student)];
+            }
+        }
+
+        answers
+            .into_iter()
+            .map(|answer| answer.map(|student| student + 1))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| "some student is left unanswered".into())
+    }
+
+    #[cfg(test)]
+    fn solve_by_simulation(self) -> Vec<usize> {
+        (1..=self.reported_by.len())
             .map(|first| {
                 let mut holes = vec![false; self.reported_by.len()];
                 let mut index = first - 1;
@@ -60,14 +84,14 @@ impl Badge {
                 }
                 index + 1
             })
-            .collect::<Vec<_>>();
-
-        Ok(solutions)
+            .collect::<Vec<_>>()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use stress::{Random, Stress};
+
     use super::*;
 
     #[test]
@@ -89,4 +113,28 @@ mod tests {
         assert_eq!(vec![1, 2, 3], actual);
         Ok(())
     }
+
+    impl Stress for Badge {
+        type Input = Self;
+        type Output = Vec<usize>;
+        const MAX_SIZE: usize = 50;
+
+        fn generate(random: &mut Random, size: usize) -> Self {
+            let reported_by = random.array(size, 1..=size);
+            Badge { reported_by }
+        }
+
+        fn reference(input: &Self) -> Vec<usize> {
+            input.clone().solve_by_simulation()
+        }
+
+        fn solve(input: &Self) -> Vec<usize> {
+            input.clone().solve().expect("the input is valid")
+        }
+    }
+
+    #[test]
+    fn test_stress() {
+        stress::check::<Badge>(2000, 0);
+    }
 }
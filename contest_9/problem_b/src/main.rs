@@ -1,48 +1,232 @@
+#[cfg(test)]
 use std::collections::HashSet;
-use std::io;
-use std::io::{BufRead, Lines};
-
-macro_rules! parse_next {
-    ($lines:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let line = $lines.next().ok_or("Missing next line")??;
-        let mut line = line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-    ($line:ident -> $(let $name:ident: $ty:ty = $range:expr);+) => {
-        let mut line = $line.split_whitespace();
-        $(
-            let $name = line.next().ok_or_else(|| format!("Missing {}", stringify!($name)))?;
-            let $name = $name.parse::<$ty>()?;
-            debug_assert!(($range).contains(&$name));
-        )+
-        debug_assert!(line.next().is_none());
-    };
-}
+
+#[cfg(test)]
+use bitset::BitVec;
+use output::{Lines, Verdict};
+use parse_next::parse_next;
+use problem::Problem;
+use scanner::{Scan, Scanner};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 type Error = Box<dyn std::error::Error>;
 
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let mut lines = stdin.lines();
-    parse_next!(lines => let query_count: usize = 1..=100);
-    let ping_pong = PingPong::new(query_count)?;
-    ping_pong.solve(lines)?;
-    Ok(())
+    problem::run::<Queries>()
 }
 
 type IntervalId = usize;
 
+#[derive(Clone, Debug)]
+struct Queries(Vec<Query>);
+
+#[derive(Copy, Clone, Debug)]
+enum Query {
+    NewInterval(i32, i32),
+    PathExists(IntervalId, IntervalId),
+}
+
+impl Scan for Queries {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        parse_next!(scanner => let query_count: usize = 1..=100_000);
+        let mut queries = Vec::with_capacity(query_count);
+        for _ in 0..query_count {
+            let mut line = scanner.next_line()?;
+            parse_next! { line ->
+                let query_type: u8 = 1..=2;
+                let first: i32 = -(10i32.pow(9))..=10i32.pow(9);
+                let second: i32 = -(10i32.pow(9))..=10i32.pow(9)
+            }
+            let query = match query_type {
+                1 => Query::NewInterval(first, second),
+                2 => Query::PathExists(first as IntervalId - 1, second as IntervalId - 1),
+                _ => unreachable!(),
+            };
+            queries.push(query);
+        }
+        Ok(Queries(queries))
+    }
+}
+
+impl Problem for Queries {
+    type Input = Self;
+    type Output = Lines<Verdict>;
+
+    fn solve(input: Self) -> Result<Lines<Verdict>> {
+        let components = Components::new(&input.0);
+        components.solve(input.0).map(Lines)
+    }
+}
+
+/// Intervals grouped into components of mutually reachable ones.
+///
+/// Every interval is longer than the earlier ones, so it reaches and is reached from each interval
+/// strictly containing one of its ends, and only from them. Such intervals are found by their
+/// components, whose spans are kept in a segment tree over the compressed coordinates, each node
+/// listing the components whose spans strictly contain all of its coordinates. Finding the
+/// components around a point takes their entries off its path in the tree, as they get merged into
+/// the component of the new interval, which is then added with its span. An interval reaches
+/// another one outside its component when one of its ends lies strictly in the span of the other
+/// component.
+struct Components {
+    intervals: Vec<Interval>,
+    /// Disjoint-set forest of the intervals, whose roots represent the components.
+    parents: Vec<IntervalId>,
+    /// Span of the component, for its root interval.
+    spans: Vec<Interval>,
+    /// Sorted distinct ends of all intervals.
+    coordinates: Vec<i32>,
+    /// Segment tree over `coordinates`, with the children of node `i` at `2i + 1` and `2i + 2`.
+    covering: Vec<Vec<IntervalId>>,
+}
+
+impl Components {
+    fn new(queries: &[Query]) -> Self {
+        let mut coordinates = queries
+            .iter()
+            .filter_map(|query| match *query {
+                Query::NewInterval(lower, upper) => Some([lower, upper]),
+                Query::PathExists(..) => None,
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        coordinates.sort_unstable();
+        coordinates.dedup();
+        let interval_count = coordinates.len() / 2;
+        Self {
+            intervals: Vec::with_capacity(interval_count),
+            parents: Vec::with_capacity(interval_count),
+            spans: Vec::with_capacity(interval_count),
+            covering: vec![Vec::new(); 4 * coordinates.len().max(1)],
+            coordinates,
+        }
+    }
+
+    fn solve(mut self, queries: Vec<Query>) -> Result<Vec<Verdict>> {
+        let mut answers = Vec::new();
+        for query in queries {
+            match query {
+                Query::NewInterval(lower, upper) => self.handle_new_interval(lower, upper)?,
+                Query::PathExists(from_id, to_id) => {
+                    let path_exists = self.handle_path_exists(from_id, to_id)?;
+                    answers.push(Verdict::yes_no(path_exists));
+                }
+            }
+        }
+        Ok(answers)
+    }
+
+    fn handle_new_interval(&mut self, lower: i32, upper: i32) -> Result<()> {
+        debug_assert!(self.intervals.last().map_or(true, |last| {
+            i64::from(last.upper()) - i64::from(last.lower()) < i64::from(upper) - i64::from(lower)
+        }));
+
+        let interval = Interval::new(lower, upper);
+        let id = self.intervals.len();
+        self.intervals.push(interval);
+        self.parents.push(id);
+        self.spans.push(interval);
+
+        for end in [lower, upper] {
+            let position = self.position(end)?;
+            for other_id in self.take_covering(position) {
+                let other_root = self.find(other_id);
+                if other_root != id {
+                    self.parents[other_root] = id;
+                    let span = self.spans[other_root];
+                    self.spans[id] = Interval::new(
+                        self.spans[id].lower().min(span.lower()),
+                        self.spans[id].upper().max(span.upper()),
+                    );
+                }
+            }
+        }
+
+        let span = self.spans[id];
+        let first = self.position(span.lower())? + 1;
+        let last = self.position(span.upper())? - 1;
+        if first <= last {
+            self.insert_covering(0, 0, self.coordinates.len() - 1, first, last, id);
+        }
+        Ok(())
+    }
+
+    fn handle_path_exists(&mut self, from_id: IntervalId, to_id: IntervalId) -> Result<bool> {
+        if from_id >= self.intervals.len() || to_id >= self.intervals.len() {
+            return Err("Query refers to an interval not added yet".into());
+        }
+        let to_root = self.find(to_id);
+        if self.find(from_id) == to_root {
+            return Ok(true);
+        }
+        let from = self.intervals[from_id];
+        let span = self.spans[to_root];
+        Ok(span.contains(from.lower()) || span.contains(from.upper()))
+    }
+
+    fn position(&self, coordinate: i32) -> Result<usize> {
+        self.coordinates
+            .binary_search(&coordinate)
+            .map_err(|_| "Missing coordinate".into())
+    }
+
+    fn find(&mut self, mut id: IntervalId) -> IntervalId {
+        while self.parents[id] != id {
+            self.parents[id] = self.parents[self.parents[id]];
+            id = self.parents[id];
+        }
+        id
+    }
+
+    /// Lists the component in the nodes covering the positions `first..=last`.
+    fn insert_covering(
+        &mut self,
+        node: usize,
+        lower: usize,
+        upper: usize,
+        first: usize,
+        last: usize,
+        id: IntervalId,
+    ) {
+        if last < lower || upper < first {
+            return;
+        }
+        if first <= lower && upper <= last {
+            self.covering[node].push(id);
+            return;
+        }
+        let middle = (lower + upper) / 2;
+        self.insert_covering(2 * node + 1, lower, middle, first, last, id);
+        self.insert_covering(2 * node + 2, middle + 1, upper, first, last, id);
+    }
+
+    /// Takes the components listed on the path to the position, those whose spans contain it.
+    fn take_covering(&mut self, position: usize) -> Vec<IntervalId> {
+        let mut taken = Vec::new();
+        let (mut node, mut lower, mut upper) = (0, 0, self.coordinates.len() - 1);
+        loop {
+            taken.append(&mut self.covering[node]);
+            if lower == upper {
+                return taken;
+            }
+            let middle = (lower + upper) / 2;
+            if position <= middle {
+                (node, upper) = (2 * node + 1, middle);
+            } else {
+                (node, lower) = (2 * node + 2, middle + 1);
+            }
+        }
+    }
+}
+
+/// Graph of the intervals searched anew for every query.
+#[cfg(test)]
 struct PingPong {
     intervals: Vec<Interval>,
     edges: Vec<HashSet<IntervalId>>,
 }
 
+#[cfg(test)]
 impl PingPong {
     fn new(query_count: usize) -> Result<Self> {
         Ok(Self {
@@ -51,21 +235,18 @@ impl PingPong {
         })
     }
 
-    fn solve(mut self, lines: Lines<impl BufRead>) -> Result<()> {
-        for line in lines {
-            let line = line?;
-            parse_next! { line ->
-                let query_type: u8 = 1..=2;
-                let first: i32 = -(10i32.pow(9))..=10i32.pow(9);
-                let second: i32 = -(10i32.pow(9))..=10i32.pow(9)
-            }
-            match query_type {
-                1 => self.handle_new_interval(first, second)?,
-                2 => self.handle_path_exists(first as IntervalId - 1, second as IntervalId - 1)?,
-                _ => unreachable!(),
+    fn solve(mut self, queries: Vec<Query>) -> Result<Vec<Verdict>> {
+        let mut answers = Vec::new();
+        for query in queries {
+            match query {
+                Query::NewInterval(lower, upper) => self.handle_new_interval(lower, upper)?,
+                Query::PathExists(from_id, to_id) => {
+                    let path_exists = self.handle_path_exists(from_id, to_id)?;
+                    answers.push(Verdict::yes_no(path_exists));
+                }
             }
         }
-        Ok(())
+        Ok(answers)
     }
 
     fn handle_new_interval(&mut self, lower: i32, upper: i32) -> Result<()> {
@@ -90,16 +271,15 @@ impl PingPong {
         Ok(())
     }
 
-    fn handle_path_exists(&mut self, from_id: IntervalId, to_id: IntervalId) -> Result<()> {
-        let mut visited = BitSet::new();
+    fn handle_path_exists(&mut self, from_id: IntervalId, to_id: IntervalId) -> Result<bool> {
+        let mut visited = BitVec::new(self.intervals.len());
         let mut stack = Vec::with_capacity(self.intervals.len());
 
         stack.push(from_id);
         while let Some(id) = stack.pop() {
             visited.insert(id);
             if id == to_id {
-                println!("YES");
-                return Ok(());
+                return Ok(true);
             }
 
             for neighbour_id in &self.edges[id] {
@@ -109,12 +289,11 @@ impl PingPong {
             }
         }
 
-        println!("NO");
-        Ok(())
+        Ok(false)
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 struct Interval(i32, i32);
 
 impl Interval {
@@ -135,21 +314,73 @@ impl Interval {
     }
 }
 
-#[derive(Copy, Clone)]
-struct BitSet(u128);
+#[cfg(test)]
+mod tests {
+    use stress::{Random, Stress};
 
-impl BitSet {
-    fn new() -> Self {
-        Self(0)
+    use super::*;
+
+    impl Stress for Queries {
+        type Input = Self;
+        type Output = Vec<Verdict>;
+        const MAX_SIZE: usize = 100;
+
+        /// Intervals with ends in a narrow range, so that they overlap a lot.
+        fn generate(random: &mut Random, size: usize) -> Self {
+            let mut queries = Vec::with_capacity(size);
+            let mut interval_count = 0;
+            let mut length = 0;
+            for _ in 0..size {
+                if interval_count >= 2 && random.chance(0.5) {
+                    let from_id = random.range(0..=interval_count - 1);
+                    let to_id = loop {
+                        let to_id = random.range(0..=interval_count - 1);
+                        if to_id != from_id {
+                            break to_id;
+                        }
+                    };
+                    queries.push(Query::PathExists(from_id, to_id));
+                } else {
+                    length += random.range(1..=3);
+                    let lower = random.range(-2 * length..=length);
+                    queries.push(Query::NewInterval(lower, lower + length));
+                    interval_count += 1;
+                }
+            }
+            Queries(queries)
+        }
+
+        fn reference(input: &Self) -> Vec<Verdict> {
+            let ping_pong = PingPong::new(input.0.len()).unwrap();
+            ping_pong.solve(input.0.clone()).unwrap()
+        }
+
+        fn solve(input: &Self) -> Vec<Verdict> {
+            Components::new(&input.0).solve(input.0.clone()).unwrap()
+        }
     }
 
-    fn insert(&mut self, index: usize) {
-        debug_assert!(index < 100);
-        self.0 |= 1u128 << index;
+    /// Interval `k` is `(k, 3k + 1)`, the ones from the second on forming a single chain.
+    #[test]
+    fn test_many_intervals() -> Result<()> {
+        let interval_count = 99_997;
+        let mut queries = (0..interval_count as i32)
+            .map(|k| Query::NewInterval(k, 3 * k + 1))
+            .collect::<Vec<_>>();
+        let last = interval_count - 1;
+        queries.extend([
+            Query::PathExists(1, last),
+            Query::PathExists(last, 1),
+            Query::PathExists(0, last),
+        ]);
+        let answers = Components::new(&queries).solve(queries)?;
+        let expected = [true, true, false].map(Verdict::yes_no);
+        assert_eq!(expected.as_slice(), answers);
+        Ok(())
     }
 
-    fn contains(&self, index: usize) -> bool {
-        debug_assert!(index < 100);
-        (self.0 >> index) & 1u128 == 1
+    #[test]
+    fn test_stress() {
+        stress::check::<Queries>(3000, 0);
     }
 }
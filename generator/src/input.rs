@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+/// Problem input written line by line, with the tokens of a line separated by spaces.
+#[derive(Clone, Debug, Default)]
+pub struct Input(String);
+
+impl Input {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a line of the items.
+    pub fn line<T: Display>(mut self, items: impl IntoIterator<Item = T>) -> Self {
+        for (index, item) in items.into_iter().enumerate() {
+            if index > 0 {
+                self.0.push(' ');
+            }
+            self.0 += &item.to_string();
+        }
+        self.0.push('\n');
+        self
+    }
+
+    /// Appends a line for every pair, such as the ends of the edges of a graph.
+    pub fn pairs<T: Display, U: Display>(self, pairs: impl IntoIterator<Item = (T, U)>) -> Self {
+        pairs.into_iter().fold(self, |input, (first, second)| {
+            input.line([first.to_string(), second.to_string()])
+        })
+    }
+}
+
+impl From<Input> for String {
+    fn from(input: Input) -> Self {
+        input.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input() {
+        let input = Input::new()
+            .line([3, 2])
+            .line(vec!['a', 'b', 'c'])
+            .pairs([(1, 'x'), (3, 'y')])
+            .line(Vec::<u8>::new());
+        assert_eq!("3 2\na b c\n1 x\n3 y\n\n", String::from(input));
+    }
+}
@@ -0,0 +1,217 @@
+//! Reproducible random inputs for problem solutions.
+//!
+//! [`Random`] draws the building blocks of inputs from a seed: integers from the constraint
+//! ranges, arrays, permutations, distinct sets, strings, trees, graphs and grid points. A problem
+//! declares its [`Generator`]s in its tests, each writing a whole input in the exact format of the
+//! problem with an [`Input`], usually one for small random inputs and one for inputs of the
+//! maximal size, sharing a function of the [`Size`] preset, and some for the shapes its solution
+//! could be slow or wrong on.
+//!
+//! [`check`] runs the solution on the inputs of every generator, scanning them in the validating
+//! mode of the scanner, so that generators breaking the constraints are caught, and [`write`]
+//! saves the inputs as files, to be run by hand or added to the examples.
+//!
+//! ```
+//! use generator::{Generator, Input, Random, Size};
+//!
+//! fn array(random: &mut Random, size: Size) -> String {
+//!     let length = size.draw(random, 1..=size.pick(10, 100_000));
+//!     Input::new()
+//!         .line([length])
+//!         .line(random.array(length, 1..=100u32))
+//!         .into()
+//! }
+//!
+//! const GENERATORS: [Generator; 2] = [
+//!     Generator::sized("random", array, Size::Small),
+//!     Generator::sized("maximal", array, Size::Maximal),
+//! ];
+//! let input = GENERATORS[0].generate(1);
+//! assert_eq!(input, GENERATORS[0].generate(1));
+//! assert!(GENERATORS[1].generate(1).starts_with("100000\n"));
+//! ```
+
+mod input;
+mod random;
+
+use std::fs;
+use std::io;
+use std::ops::{Range, RangeInclusive};
+use std::path::Path;
+
+use problem::Problem;
+use scanner::StringScanner;
+
+pub use input::Input;
+pub use random::{Integer, Random};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+type Error = Box<dyn std::error::Error>;
+
+/// Named generator of whole problem inputs.
+#[derive(Copy, Clone)]
+pub struct Generator {
+    pub name: &'static str,
+    generate: Generate,
+}
+
+#[derive(Copy, Clone)]
+enum Generate {
+    Fixed(fn(&mut Random) -> String),
+    Sized(fn(&mut Random, Size) -> String, Size),
+}
+
+impl Generator {
+    pub const fn new(name: &'static str, generate: fn(&mut Random) -> String) -> Self {
+        Self {
+            name,
+            generate: Generate::Fixed(generate),
+        }
+    }
+
+    /// Generator of inputs of the preset size, written by a function shared between the presets.
+    pub const fn sized(
+        name: &'static str,
+        generate: fn(&mut Random, Size) -> String,
+        size: Size,
+    ) -> Self {
+        Self {
+            name,
+            generate: Generate::Sized(generate, size),
+        }
+    }
+
+    /// Generates the input for the seed.
+    pub fn generate(&self, seed: u64) -> String {
+        let random = &mut Random::new(seed);
+        match self.generate {
+            Generate::Fixed(generate) => generate(random),
+            Generate::Sized(generate, size) => generate(random, size),
+        }
+    }
+}
+
+/// Size preset of generated inputs: small ones, easy to read and within reach of a brute force, or
+/// maximal ones, at the limits of the constraints.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Size {
+    Small,
+    Maximal,
+}
+
+impl Size {
+    /// Returns the bound of the preset.
+    pub fn pick<T>(self, small: T, maximal: T) -> T {
+        match self {
+            Size::Small => small,
+            Size::Maximal => maximal,
+        }
+    }
+
+    /// Draws a number from the range for small inputs and returns its end for maximal ones.
+    pub fn draw<T: Integer>(self, random: &mut Random, range: RangeInclusive<T>) -> T {
+        match self {
+            Size::Small => random.range(range),
+            Size::Maximal => *range.end(),
+        }
+    }
+}
+
+/// Solves the input of every generator for every seed, failing with the name of the first
+/// generator whose input is invalid or which the solution fails on.
+pub fn check<P: Problem>(generators: &[Generator], seeds: Range<u64>) -> Result<()> {
+    for seed in seeds {
+        for generator in generators {
+            let input = generator.generate(seed);
+            let scanner = StringScanner::from(input.as_str()).validating();
+            problem::run_with::<P>(scanner, io::sink())
+                .map_err(|error| format!("generator {}, seed {seed}: {error}", generator.name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the input of every generator for the seed to `<name>.in` in the directory.
+pub fn write(generators: &[Generator], seed: u64, directory: impl AsRef<Path>) -> io::Result<()> {
+    fs::create_dir_all(&directory)?;
+    for generator in generators {
+        let path = directory.as_ref().join(format!("{}.in", generator.name));
+        fs::write(path, generator.generate(seed))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use scanner::{LineScanner, Scan, Scanner};
+
+    use super::*;
+
+    /// Sum of up to ten numbers from `1..=100`.
+    struct Sum(Vec<u32>);
+
+    impl Scan for Sum {
+        fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+            let length = scanner.next_line()?.next_ranged(1..=10)?;
+            Ok(Sum(scanner.next_line()?.next_vec(length, 1..=100)?))
+        }
+    }
+
+    impl Problem for Sum {
+        type Input = Self;
+        type Output = u32;
+
+        fn solve(input: Self) -> Result<u32> {
+            Ok(input.0.into_iter().sum())
+        }
+    }
+
+    fn numbers(length: usize, values: Vec<u32>) -> String {
+        Input::new().line([length]).line(values).into()
+    }
+
+    fn sum(random: &mut Random, size: Size) -> String {
+        let length = size.draw(random, 1..=10);
+        numbers(length, random.array(length, 1..=size.pick(10, 100)))
+    }
+
+    const RANDOM: Generator = Generator::sized("random", sum, Size::Small);
+    const MAXIMAL: Generator = Generator::new("maximal", |_| numbers(10, vec![100; 10]));
+    const TOO_LONG: Generator = Generator::new("too_long", |_| numbers(11, vec![1; 11]));
+
+    #[test]
+    fn test_check() {
+        assert!(check::<Sum>(&[RANDOM, MAXIMAL], 0..10).is_ok());
+        assert_eq!(
+            "generator too_long, seed 0: line 1, token 1: `11` is out of range",
+            check::<Sum>(&[RANDOM, TOO_LONG], 0..10)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_size() {
+        let random = &mut Random::new(0);
+        assert_eq!(10, Size::Maximal.draw(random, 1..=10));
+        assert!((1..=10).contains(&Size::Small.draw(random, 1..=10)));
+        assert!(Generator::sized("maximal", sum, Size::Maximal)
+            .generate(0)
+            .starts_with("10\n"));
+    }
+
+    #[test]
+    fn test_write() -> Result<()> {
+        let directory = std::env::temp_dir().join("generator-tests");
+        write(&[RANDOM, MAXIMAL], 7, &directory)?;
+        assert_eq!(
+            RANDOM.generate(7),
+            fs::read_to_string(directory.join("random.in"))?
+        );
+        assert_eq!(
+            numbers(10, vec![100; 10]),
+            fs::read_to_string(directory.join("maximal.in"))?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,347 @@
+//! The seedable generator and the random structures built with it.
+
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+/// Small, seedable pseudorandom number generator, the SplitMix64 algorithm. Equal seeds give equal
+/// sequences on every platform, so generated inputs are reproducible from their seed.
+#[derive(Clone, Debug)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        value ^ (value >> 31)
+    }
+
+    /// Returns an integer from the range, almost uniformly for ranges much smaller than `u64`.
+    pub fn range<T: Integer>(&mut self, range: RangeInclusive<T>) -> T {
+        let (start, end) = (range.start().to_i128(), range.end().to_i128());
+        debug_assert!(start <= end);
+        let length = (end - start + 1) as u128;
+        let offset = u128::from(self.next_u64()) % length;
+        T::from_i128(start + offset as i128)
+    }
+
+    /// Returns true with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let fraction = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        fraction < probability
+    }
+
+    /// Returns `length` integers from the range.
+    pub fn array<T: Integer>(&mut self, length: usize, range: RangeInclusive<T>) -> Vec<T> {
+        (0..length).map(|_| self.range(range.clone())).collect()
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.range(0..=index));
+        }
+    }
+
+    /// Returns a permutation of `1..=length`.
+    pub fn permutation(&mut self, length: usize) -> Vec<usize> {
+        let mut permutation = (1..=length).collect::<Vec<_>>();
+        self.shuffle(&mut permutation);
+        permutation
+    }
+
+    /// Returns `count` distinct integers from the range, in random order. Uses Floyd's sampling,
+    /// so the time depends on `count` only, however large the range.
+    pub fn distinct<T: Integer>(&mut self, count: usize, range: RangeInclusive<T>) -> Vec<T> {
+        let (start, end) = (range.start().to_i128(), range.end().to_i128());
+        let length = end - start + 1;
+        debug_assert!(count as i128 <= length);
+        let mut chosen = HashSet::with_capacity(count);
+        let mut values = Vec::with_capacity(count);
+        for last in length - count as i128..length {
+            let offset = self.range(0..=last);
+            let offset = if chosen.insert(offset) {
+                offset
+            } else {
+                chosen.insert(last);
+                last
+            };
+            values.push(T::from_i128(start + offset));
+        }
+        self.shuffle(&mut values);
+        values
+    }
+
+    /// Returns a string of `length` characters from the range.
+    pub fn string(&mut self, length: usize, characters: RangeInclusive<char>) -> String {
+        let (start, end) = (u32::from(*characters.start()), u32::from(*characters.end()));
+        (0..length)
+            .map(|_| char::from_u32(self.range(start..=end)).unwrap_or(*characters.start()))
+            .collect()
+    }
+
+    /// Returns a string of `length` lowercase English letters.
+    pub fn lowercase(&mut self, length: usize) -> String {
+        self.string(length, 'a'..='z')
+    }
+
+    /// Returns the edges of a random tree on the vertices `1..=vertex_count`, each vertex joined to
+    /// a random earlier one in a random order of the vertices, which keeps the tree shallow.
+    pub fn tree(&mut self, vertex_count: usize) -> Vec<(usize, usize)> {
+        let order = self.permutation(vertex_count);
+        let mut edges = (1..vertex_count)
+            .map(|index| {
+                let parent = order[self.range(0..=index - 1)];
+                self.edge(parent, order[index])
+            })
+            .collect::<Vec<_>>();
+        self.shuffle(&mut edges);
+        edges
+    }
+
+    /// Returns the edges of a path through the vertices `1..=vertex_count` in a random order, the
+    /// deepest tree there is.
+    pub fn path(&mut self, vertex_count: usize) -> Vec<(usize, usize)> {
+        let order = self.permutation(vertex_count);
+        let mut edges = order
+            .windows(2)
+            .map(|pair| self.edge(pair[0], pair[1]))
+            .collect::<Vec<_>>();
+        self.shuffle(&mut edges);
+        edges
+    }
+
+    /// Returns `edge_count` distinct edges between distinct vertices of `1..=vertex_count`.
+    pub fn simple_graph(&mut self, vertex_count: usize, edge_count: usize) -> Vec<(usize, usize)> {
+        let pair_count = vertex_count * vertex_count.saturating_sub(1) / 2;
+        debug_assert!(edge_count <= pair_count);
+        self.distinct(edge_count, 0..=pair_count.wrapping_sub(1))
+            .into_iter()
+            .map(|index| {
+                let (first, second) = unordered_pair(vertex_count, index);
+                self.edge(first, second)
+            })
+            .collect()
+    }
+
+    /// Returns `edge_count` distinct edges between distinct vertices of `1..=vertex_count` joining
+    /// all of them, a random tree with random edges added. The edges are added by rejection, so
+    /// nearly complete graphs are slow to generate.
+    pub fn connected_graph(
+        &mut self,
+        vertex_count: usize,
+        edge_count: usize,
+    ) -> Vec<(usize, usize)> {
+        debug_assert!(vertex_count >= 1 && edge_count + 1 >= vertex_count);
+        debug_assert!(edge_count <= vertex_count * (vertex_count - 1) / 2);
+        let mut edges = self.tree(vertex_count);
+        let mut present = edges
+            .iter()
+            .map(|&(first, second)| (first.min(second), first.max(second)))
+            .collect::<HashSet<_>>();
+        while edges.len() < edge_count {
+            let first = self.range(1..=vertex_count);
+            let second = self.range(1..=vertex_count);
+            if first != second && present.insert((first.min(second), first.max(second))) {
+                edges.push((first, second));
+            }
+        }
+        self.shuffle(&mut edges);
+        edges
+    }
+
+    /// Returns `count` distinct points with coordinates from the ranges.
+    pub fn grid_points(
+        &mut self,
+        count: usize,
+        x: RangeInclusive<i64>,
+        y: RangeInclusive<i64>,
+    ) -> Vec<(i64, i64)> {
+        let width = x.end() - x.start() + 1;
+        let height = y.end() - y.start() + 1;
+        self.distinct(count, 0..=width * height - 1)
+            .into_iter()
+            .map(|index| (x.start() + index % width, y.start() + index / width))
+            .collect()
+    }
+
+    /// Orients the edge randomly.
+    fn edge(&mut self, first: usize, second: usize) -> (usize, usize) {
+        if self.chance(0.5) {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+}
+
+/// Returns the pair of vertices `1 <= first < second <= vertex_count` with the given index in the
+/// lexicographic order of such pairs.
+fn unordered_pair(vertex_count: usize, index: usize) -> (usize, usize) {
+    // Pairs whose first vertex is before `first`, counting from zero.
+    let pairs_before = |first: usize| first * (2 * vertex_count - first - 1) / 2;
+    let (mut low, mut high) = (0, vertex_count - 1);
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+        if pairs_before(middle) <= index {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    let second = low + 1 + (index - pairs_before(low));
+    (low + 1, second + 1)
+}
+
+/// Primitive integers that random values can be drawn from.
+pub trait Integer: Copy {
+    fn to_i128(self) -> i128;
+
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($ty:ty),+) => {
+        $(
+            impl Integer for $ty {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Self {
+                    value as $ty
+                }
+            }
+        )+
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        let mut random = Random::new(1);
+        let values = random.array(1000, 3..=5);
+        assert!(values.iter().all(|value| (3..=5).contains(value)));
+        assert!((3..=5).all(|value| values.contains(&value)));
+        assert!(random.array(100, -2i8..=-1).iter().all(|&value| value < 0));
+        assert!(random.range(0..=u64::MAX) > 0);
+        assert!(random.range(i64::MIN..=i64::MIN + 1) < 0);
+        assert_ne!(Random::new(1).next_u64(), Random::new(2).next_u64());
+        assert_eq!(Random::new(7).next_u64(), Random::new(7).next_u64());
+        assert!(!random.chance(0.0));
+        assert!(random.chance(1.0));
+    }
+
+    #[test]
+    fn test_permutation() {
+        let mut permutation = Random::new(2).permutation(50);
+        assert_ne!((1..=50).collect::<Vec<_>>(), permutation);
+        permutation.sort_unstable();
+        assert_eq!((1..=50).collect::<Vec<_>>(), permutation);
+    }
+
+    #[test]
+    fn test_distinct() {
+        let mut random = Random::new(3);
+        let mut all = random.distinct(10, 1..=10u8);
+        all.sort_unstable();
+        assert_eq!((1..=10).collect::<Vec<_>>(), all);
+        let sparse = random.distinct(100, 0..=10u64.pow(18));
+        assert_eq!(100, sparse.iter().collect::<HashSet<_>>().len());
+        assert!(random.distinct(0, 5..=5u32).is_empty());
+    }
+
+    #[test]
+    fn test_strings() {
+        let mut random = Random::new(4);
+        let word = random.lowercase(100);
+        assert_eq!(100, word.len());
+        assert!(word.chars().all(|letter| letter.is_ascii_lowercase()));
+        assert!(random
+            .string(50, 'a'..='b')
+            .chars()
+            .all(|letter| letter <= 'b'));
+    }
+
+    /// Checks that the edges form a simple graph and returns whether it is connected.
+    fn is_connected(vertex_count: usize, edges: &[(usize, usize)]) -> bool {
+        let mut neighbours = vec![Vec::new(); vertex_count + 1];
+        let mut present = HashSet::new();
+        for &(first, second) in edges {
+            assert!((1..=vertex_count).contains(&first) && (1..=vertex_count).contains(&second));
+            assert_ne!(first, second);
+            assert!(present.insert((first.min(second), first.max(second))));
+            neighbours[first].push(second);
+            neighbours[second].push(first);
+        }
+        let mut visited = vec![false; vertex_count + 1];
+        let mut stack = vec![1];
+        visited[1] = true;
+        while let Some(vertex) = stack.pop() {
+            for &neighbour in &neighbours[vertex] {
+                if !visited[neighbour] {
+                    visited[neighbour] = true;
+                    stack.push(neighbour);
+                }
+            }
+        }
+        visited[1..].iter().all(|&visited| visited)
+    }
+
+    #[test]
+    fn test_graphs() {
+        let mut random = Random::new(5);
+        for vertex_count in 1..=30 {
+            let tree = random.tree(vertex_count);
+            assert_eq!(vertex_count - 1, tree.len());
+            assert!(is_connected(vertex_count, &tree));
+            let path = random.path(vertex_count);
+            assert!(is_connected(vertex_count, &path));
+            let maximum = vertex_count * (vertex_count - 1) / 2;
+            let connected = random.connected_graph(vertex_count, (vertex_count * 2).min(maximum));
+            assert!(is_connected(vertex_count, &connected));
+            for edge_count in [0, maximum / 2, maximum] {
+                let graph = random.simple_graph(vertex_count, edge_count);
+                assert_eq!(edge_count, graph.len());
+                is_connected(vertex_count, &graph);
+            }
+        }
+        let mut degrees = [0; 6];
+        for (first, second) in random.path(5) {
+            degrees[first] += 1;
+            degrees[second] += 1;
+        }
+        assert_eq!(2, degrees.iter().filter(|&&degree| degree == 1).count());
+    }
+
+    #[test]
+    fn test_unordered_pair() {
+        let pairs = (0..10)
+            .map(|index| unordered_pair(5, index))
+            .collect::<Vec<_>>();
+        let expected = (1..=5)
+            .flat_map(|first| (first + 1..=5).map(move |second| (first, second)))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, pairs);
+    }
+
+    #[test]
+    fn test_grid_points() {
+        let points = Random::new(6).grid_points(12, -1..=1, 0..=3);
+        assert_eq!(12, points.iter().collect::<HashSet<_>>().len());
+        assert!(points
+            .iter()
+            .all(|&(x, y)| (-1..=1).contains(&x) && (0..=3).contains(&y)));
+    }
+}
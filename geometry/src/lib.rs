@@ -0,0 +1,73 @@
+//! Points, segments and axis-aligned rectangles in the plane and in space.
+//!
+//! The types are generic over a [`Scalar`] coordinate. Predicates on integer coordinates, such as
+//! [`orientation`] or [`Segment::intersects`], only multiply, subtract and compare, so they are
+//! exact as long as the products fit in the type, which is why problems usually widen their input
+//! coordinates before building points. As their differences may be negative, they take a
+//! [`SignedScalar`]. Floating point results are compared with a [`Tolerance`] instead.
+//!
+//! ```
+//! use std::cmp::Ordering;
+//!
+//! use geometry::{orientation, Point, Rectangle, Segment};
+//!
+//! let (a, b, c) = (Point::new(0, 0), Point::new(4, 0), Point::new(1, 3));
+//! assert_eq!(Ordering::Greater, orientation(a, b, c));
+//! assert!(Segment::new(a, c).intersects(&Segment::new(Point::new(0, 3), b)));
+//!
+//! let rectangle = Rectangle::from_corners(c, a);
+//! assert_eq!(3, rectangle.area());
+//! ```
+
+mod point;
+mod rectangle;
+mod segment;
+mod tolerance;
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+pub use point::{Point, Point3, Vector, Vector3};
+pub use rectangle::Rectangle;
+pub use segment::{orientation, Segment};
+pub use tolerance::Tolerance;
+
+/// A number that points can be built of.
+pub trait Scalar:
+    Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+}
+
+macro_rules! impl_scalar {
+    ($($type:ty => $zero:expr),*) => {
+        $(
+            impl Scalar for $type {
+                const ZERO: Self = $zero;
+            }
+        )*
+    };
+}
+
+impl_scalar!(
+    i32 => 0, i64 => 0, i128 => 0, u32 => 0, u64 => 0, u128 => 0, usize => 0, f64 => 0.0
+);
+
+/// A scalar with negative values, required by the cross product and the predicates built on it,
+/// whose differences of products would overflow an unsigned type.
+///
+/// ```compile_fail
+/// use geometry::Point;
+///
+/// Point::new(1u32, 0).cross(Point::new(0, 1));
+/// ```
+pub trait SignedScalar: Scalar + Neg<Output = Self> {}
+
+macro_rules! impl_signed_scalar {
+    ($($type:ty),*) => {
+        $(
+            impl SignedScalar for $type {}
+        )*
+    };
+}
+
+impl_signed_scalar!(i32, i64, i128, f64);
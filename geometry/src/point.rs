@@ -0,0 +1,185 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::{Scalar, SignedScalar};
+
+/// A point in the plane, or the vector from the origin to it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point in space, or the vector from the origin to it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Differences of points, which are points themselves.
+pub type Vector<T> = Point<T>;
+pub type Vector3<T> = Point3<T>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts the coordinates, usually to a wider type.
+    pub fn map<U>(self, mut convert: impl FnMut(T) -> U) -> Point<U> {
+        Point::new(convert(self.x), convert(self.y))
+    }
+}
+
+impl<T: Scalar> Point<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn norm_squared(self) -> T {
+        self.dot(self)
+    }
+}
+
+impl<T: SignedScalar> Point<T> {
+    /// Returns the `z` coordinate of the cross product, positive if `other` is counterclockwise
+    /// from `self`.
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl Point<f64> {
+    pub fn norm(self) -> f64 {
+        self.norm_squared().sqrt()
+    }
+
+    pub fn distance(self, other: Self) -> f64 {
+        (other - self).norm()
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Converts the coordinates, usually to a wider type.
+    pub fn map<U>(self, mut convert: impl FnMut(T) -> U) -> Point3<U> {
+        Point3::new(convert(self.x), convert(self.y), convert(self.z))
+    }
+}
+
+impl<T: Scalar> Point3<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn norm_squared(self) -> T {
+        self.dot(self)
+    }
+}
+
+impl<T: SignedScalar> Point3<T> {
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl Point3<f64> {
+    pub fn norm(self) -> f64 {
+        self.norm_squared().sqrt()
+    }
+
+    pub fn distance(self, other: Self) -> f64 {
+        (other - self).norm()
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+macro_rules! impl_operators {
+    ($type:ident { $($coordinate:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $type<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($coordinate: self.$coordinate + other.$coordinate),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $type<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($coordinate: self.$coordinate - other.$coordinate),* }
+            }
+        }
+
+        /// Scales the vector.
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $type<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                Self { $($coordinate: self.$coordinate * factor),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $type<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($coordinate: -self.$coordinate),* }
+            }
+        }
+    };
+}
+
+impl_operators!(Point { x, y });
+impl_operators!(Point3 { x, y, z });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point() {
+        let (a, b) = (Point::new(3, 4), Point::new(-4, 3));
+        assert_eq!(Point::new(-1, 7), a + b);
+        assert_eq!(Point::new(7, 1), a - b);
+        assert_eq!(Point::new(6, 8), a * 2);
+        assert_eq!(Point::new(-3, -4), -a);
+        assert_eq!(0, a.dot(b));
+        assert_eq!(25, a.cross(b));
+        assert_eq!(-25, b.cross(a));
+        assert_eq!(25, a.norm_squared());
+        assert_eq!(5.0, a.map(f64::from).norm());
+        assert_eq!(Point::new(3i64, 4), Point::from((3i16, 4)).map(i64::from));
+    }
+
+    #[test]
+    fn test_point3() {
+        let (x, y) = (Point3::new(1, 0, 0), Point3::new(0, 1, 0));
+        assert_eq!(Point3::new(0, 0, 1), x.cross(y));
+        assert_eq!(Point3::new(0, 0, -1), y.cross(x));
+        assert_eq!(0, x.dot(y));
+        let (a, b) = (Point3::new(1.0, 2.0, 3.0), Point3::new(3.0, 5.0, 9.0));
+        assert_eq!(7.0, a.distance(b));
+        assert_eq!(Point3::new(2.0, 3.0, 6.0), b - a);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{Point, Scalar};
+
+/// An axis-aligned rectangle, including its boundary. It may be degenerate, with a zero width or
+/// height.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rectangle<T> {
+    /// The bottom left corner.
+    pub min: Point<T>,
+    /// The top right corner.
+    pub max: Point<T>,
+}
+
+impl<T: Scalar> Rectangle<T> {
+    pub fn new(min: Point<T>, max: Point<T>) -> Self {
+        debug_assert!(min.x <= max.x && min.y <= max.y);
+        Self { min, max }
+    }
+
+    /// Returns the rectangle with the given opposite corners.
+    pub fn from_corners(first: Point<T>, second: Point<T>) -> Self {
+        let (min_x, max_x) = min_max(first.x, second.x);
+        let (min_y, max_y) = min_max(first.y, second.y);
+        Self::new(Point::new(min_x, min_y), Point::new(max_x, max_y))
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+    }
+
+    /// Returns the common part of the rectangles, unless they are disjoint. Rectangles touching
+    /// along an edge or at a corner have a degenerate common part.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Point::new(
+            larger(self.min.x, other.min.x),
+            larger(self.min.y, other.min.y),
+        );
+        let max = Point::new(
+            smaller(self.max.x, other.max.x),
+            smaller(self.max.y, other.max.y),
+        );
+        (min.x <= max.x && min.y <= max.y).then(|| Self::new(min, max))
+    }
+
+    /// Returns the smallest rectangle containing both.
+    pub fn union(&self, other: &Self) -> Self {
+        let min = Point::new(
+            smaller(self.min.x, other.min.x),
+            smaller(self.min.y, other.min.y),
+        );
+        let max = Point::new(
+            larger(self.max.x, other.max.x),
+            larger(self.max.y, other.max.y),
+        );
+        Self::new(min, max)
+    }
+
+    /// Multiplies all coordinates, scaling the rectangle around the origin.
+    pub fn scale(&self, factor: T) -> Self {
+        Self::new(self.min * factor, self.max * factor)
+    }
+}
+
+/// The minimum and maximum of partially ordered scalars, for which `Ord::min` is not available.
+fn smaller<T: Scalar>(first: T, second: T) -> T {
+    min_max(first, second).0
+}
+
+fn larger<T: Scalar>(first: T, second: T) -> T {
+    min_max(first, second).1
+}
+
+fn min_max<T: Scalar>(first: T, second: T) -> (T, T) {
+    if second < first {
+        (second, first)
+    } else {
+        (first, second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangle(min: (u32, u32), max: (u32, u32)) -> Rectangle<u32> {
+        Rectangle::new(Point::from(min), Point::from(max))
+    }
+
+    #[test]
+    fn test_rectangle() {
+        let first = rectangle((0, 0), (4, 3));
+        assert_eq!(4, first.width());
+        assert_eq!(3, first.height());
+        assert_eq!(12, first.area());
+        assert!(first.contains(Point::new(4, 0)));
+        assert!(!first.contains(Point::new(5, 1)));
+        assert_eq!(rectangle((0, 0), (8, 6)), first.scale(2));
+        assert_eq!(
+            rectangle((1, 2), (5, 7)),
+            Rectangle::from_corners(Point::new(5, 2), Point::new(1, 7))
+        );
+    }
+
+    #[test]
+    fn test_intersection_and_union() {
+        let first = rectangle((0, 0), (4, 3));
+        let second = rectangle((2, 1), (6, 5));
+        assert_eq!(Some(rectangle((2, 1), (4, 3))), first.intersection(&second));
+        assert_eq!(rectangle((0, 0), (6, 5)), first.union(&second));
+        let touching = rectangle((4, 3), (5, 5));
+        assert_eq!(
+            Some(0),
+            first.intersection(&touching).map(|common| common.area())
+        );
+        assert_eq!(None, first.intersection(&rectangle((5, 0), (6, 1))));
+        assert_eq!(None, rectangle((0, 4), (1, 5)).intersection(&first));
+    }
+}
@@ -0,0 +1,110 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Mul};
+
+use crate::{Point, Rectangle, Scalar, SignedScalar};
+
+/// Returns whether `c` lies to the left of the directed line from `a` to `b`, as
+/// [`Ordering::Greater`], on it, as [`Ordering::Equal`], or to its right.
+pub fn orientation<T: SignedScalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> Ordering {
+    let cross = (b - a).cross(c - a);
+    cross.partial_cmp(&T::ZERO).unwrap_or(Ordering::Equal)
+}
+
+/// The segment between two points, including both ends.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Segment<P> {
+    pub start: P,
+    pub end: P,
+}
+
+impl<P> Segment<P> {
+    pub const fn new(start: P, end: P) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<P: Copy + Add<Output = P> + Mul<f64, Output = P>> Segment<P> {
+    /// Returns the point a fraction `time` of the way from the start to the end.
+    pub fn point(&self, time: f64) -> P {
+        debug_assert!((0.0..=1.0).contains(&time));
+        self.start * (1.0 - time) + self.end * time
+    }
+}
+
+impl<T: Scalar> Segment<Point<T>> {
+    /// Returns the smallest rectangle containing the segment.
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        Rectangle::from_corners(self.start, self.end)
+    }
+}
+
+impl<T: SignedScalar> Segment<Point<T>> {
+    pub fn contains(&self, point: Point<T>) -> bool {
+        orientation(self.start, self.end, point) == Ordering::Equal
+            && self.bounding_box().contains(point)
+    }
+
+    /// Returns whether the segments have a common point, including their ends.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let sides = |segment: &Self, other: &Self| {
+            (
+                orientation(segment.start, segment.end, other.start),
+                orientation(segment.start, segment.end, other.end),
+            )
+        };
+        let (first, second) = (sides(self, other), sides(other, self));
+        if first.0 != first.1
+            && first.0 != Ordering::Equal
+            && first.1 != Ordering::Equal
+            && second.0 != second.1
+            && second.0 != Ordering::Equal
+            && second.1 != Ordering::Equal
+        {
+            return true;
+        }
+        self.contains(other.start)
+            || self.contains(other.end)
+            || other.contains(self.start)
+            || other.contains(self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point3;
+
+    #[test]
+    fn test_orientation() {
+        let (a, b) = (Point::new(0, 0), Point::new(2, 2));
+        assert_eq!(Ordering::Greater, orientation(a, b, Point::new(0, 1)));
+        assert_eq!(Ordering::Less, orientation(a, b, Point::new(1, 0)));
+        assert_eq!(Ordering::Equal, orientation(a, b, Point::new(5, 5)));
+        let large = Point::new(1_000_000_000i64, 999_999_999);
+        assert_eq!(Ordering::Greater, orientation(a, large, Point::new(1, 1)));
+    }
+
+    #[test]
+    fn test_intersects() {
+        let segment = |a: (i32, i32), b: (i32, i32)| Segment::new(Point::from(a), Point::from(b));
+        let diagonal = segment((0, 0), (4, 4));
+        assert!(diagonal.intersects(&segment((0, 4), (4, 0))));
+        assert!(diagonal.intersects(&segment((2, 2), (5, 0))));
+        assert!(diagonal.intersects(&segment((4, 4), (6, 6))));
+        assert!(diagonal.intersects(&segment((1, 1), (2, 2))));
+        assert!(!diagonal.intersects(&segment((5, 5), (6, 6))));
+        assert!(!diagonal.intersects(&segment((1, 0), (3, 2))));
+        assert!(!diagonal.intersects(&segment((0, 4), (1, 2))));
+        let point = segment((2, 2), (2, 2));
+        assert!(diagonal.intersects(&point));
+        assert!(!segment((0, 1), (4, 5)).intersects(&point));
+    }
+
+    #[test]
+    fn test_point() {
+        let segment = Segment::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0));
+        assert_eq!(Point3::new(0.0, 0.0, 0.0), segment.point(0.0));
+        assert_eq!(Point3::new(1.0, 1.0, 0.0), segment.point(1.0));
+        assert_eq!(Point3::new(0.25, 0.25, 0.0), segment.point(0.25));
+    }
+}
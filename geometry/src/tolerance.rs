@@ -0,0 +1,67 @@
+use std::cmp::Ordering;
+
+/// Bounds within which floats computed in different ways are considered equal, by their absolute
+/// or their relative difference, like the checkers of floating point answers.
+#[derive(Copy, Clone, Debug)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Tolerance {
+    /// Uses the same bound for the absolute and the relative difference.
+    pub const fn new(epsilon: f64) -> Self {
+        Self {
+            absolute: epsilon,
+            relative: epsilon,
+        }
+    }
+
+    pub fn approx_eq(&self, first: f64, second: f64) -> bool {
+        let difference = (first - second).abs();
+        difference <= self.absolute || difference <= self.relative * first.abs().max(second.abs())
+    }
+
+    /// Compares the floats, treating the ones within the tolerance as equal.
+    pub fn approx_cmp(&self, first: f64, second: f64) -> Ordering {
+        if self.approx_eq(first, second) {
+            Ordering::Equal
+        } else {
+            first.total_cmp(&second)
+        }
+    }
+
+    /// Returns the sign of the float, zero within the absolute tolerance.
+    pub fn sign(&self, value: f64) -> Ordering {
+        if value.abs() <= self.absolute {
+            Ordering::Equal
+        } else {
+            value.total_cmp(&0.0)
+        }
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self::new(1e-9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tolerance() {
+        let tolerance = Tolerance::new(1e-6);
+        assert!(tolerance.approx_eq(0.1 + 0.2, 0.3));
+        assert!(tolerance.approx_eq(1e12, 1e12 + 1.0));
+        assert!(!tolerance.approx_eq(1.0, 1.001));
+        assert_eq!(Ordering::Equal, tolerance.approx_cmp(1.0, 1.0 + 1e-9));
+        assert_eq!(Ordering::Less, tolerance.approx_cmp(1.0, 1.1));
+        assert_eq!(Ordering::Greater, tolerance.approx_cmp(-1.0, -1.1));
+        assert_eq!(Ordering::Equal, tolerance.sign(-1e-7));
+        assert_eq!(Ordering::Less, tolerance.sign(-1e-3));
+        assert_eq!(Ordering::Equal, Tolerance::default().approx_cmp(0.0, -0.0));
+    }
+}
@@ -0,0 +1,171 @@
+//! End-to-end tests running a problem binary on its example files.
+//!
+//! Every problem crate keeps examples as `tests/examples/NN.in` and `NN.out` pairs and runs them
+//! from an integration test, `tests/examples.rs`, consisting of a single [`test_examples!`]
+//! invocation. Each input is piped to the compiled binary, so the whole path from scanning the
+//! input to writing the answer is exercised, and the output is judged by a [`Checker`], by default
+//! compared with the expected one token by token. Problems accepting other answers name their
+//! checker, as in `harness::test_examples!(checker::Floats::new(1e-6))`. Rejected examples are
+//! reported together, each with a line by line diff of the two outputs.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+pub use checker;
+use checker::{Checker, Exact};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+type Error = Box<dyn std::error::Error>;
+
+/// Defines the `test_examples` test of a problem crate, running its binary on the examples in
+/// `tests/examples` and judging the outputs with the given checker, [`Exact`] by default.
+#[macro_export]
+macro_rules! test_examples {
+    () => {
+        $crate::test_examples!($crate::checker::Exact);
+    };
+    ($checker:expr) => {
+        #[test]
+        fn test_examples() {
+            $crate::check_examples_with(
+                env!(concat!("CARGO_BIN_EXE_", env!("CARGO_PKG_NAME"))),
+                concat!(env!("CARGO_MANIFEST_DIR"), "/tests/examples"),
+                &$checker,
+            );
+        }
+    };
+}
+
+/// Runs the executable on every example in the directory, panicking with the diffs of those whose
+/// output does not match the expected one exactly.
+#[track_caller]
+pub fn check_examples(executable: impl AsRef<Path>, directory: impl AsRef<Path>) {
+    check_examples_with(executable, directory, &Exact);
+}
+
+/// Runs the executable on every example in the directory, panicking with the diffs of those whose
+/// output the checker rejects.
+#[track_caller]
+pub fn check_examples_with(
+    executable: impl AsRef<Path>,
+    directory: impl AsRef<Path>,
+    checker: &impl Checker,
+) {
+    let examples = examples(directory.as_ref())
+        .unwrap_or_else(|error| panic!("cannot read the examples: {error}"));
+    assert!(!examples.is_empty(), "there are no examples");
+
+    let mut report = String::new();
+    let mut failures = 0;
+    for example in &examples {
+        if let Err(error) = run(executable.as_ref(), example, checker) {
+            let _ = writeln!(report, "example {}: {error}", example.name);
+            failures += 1;
+        }
+    }
+    assert!(
+        failures == 0,
+        "{report}{failures} of {} examples failed",
+        examples.len()
+    );
+}
+
+struct Example {
+    name: String,
+    input: PathBuf,
+    output: PathBuf,
+}
+
+/// Returns the `NN.in` files of the directory paired with their `NN.out` files, sorted by name.
+fn examples(directory: &Path) -> Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let input = entry?.path();
+        if input
+            .extension()
+            .map_or(true, |extension| extension != "in")
+        {
+            continue;
+        }
+        let name = input
+            .file_stem()
+            .ok_or("Missing example name")?
+            .to_string_lossy()
+            .into_owned();
+        let output = input.with_extension("out");
+        if !output.is_file() {
+            return Err(format!("example {name} has no output file").into());
+        }
+        examples.push(Example {
+            name,
+            input,
+            output,
+        });
+    }
+    examples.sort_by(|first, second| first.name.cmp(&second.name));
+    Ok(examples)
+}
+
+fn run(executable: &Path, example: &Example, checker: &impl Checker) -> Result<()> {
+    let input = fs::read_to_string(&example.input)?;
+    let expected = fs::read_to_string(&example.output)?;
+
+    let mut child = Command::new(executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .ok_or("Missing standard input")?
+        .write_all(input.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{}\n{}", output.status, stderr.trim_end()).into());
+    }
+    let actual = String::from_utf8(output.stdout)?;
+    checker
+        .check(&input, &expected, &actual)
+        .map_err(|error| format!("{error}\n{}", diff(&expected, &actual)).into())
+}
+
+/// Lists the lines of both outputs, marking those missing from the actual one with `-` and those
+/// unexpected in it with `+`.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let mut diff = String::new();
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if expected.trim_end() == actual.trim_end() => {
+                let _ = writeln!(diff, "  {expected}");
+            }
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    let _ = writeln!(diff, "- {expected}");
+                }
+                if let Some(actual) = actual {
+                    let _ = writeln!(diff, "+ {actual}");
+                }
+            }
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!("  1 2\n- 3\n+ 4\n", diff("1 2\n3\n", "1 2\n4\n"));
+        assert_eq!("  YES\n- NO\n", diff("YES\nNO\n", "YES\n"));
+        assert_eq!("- 1\n+ 1 2\n  3\n", diff("1\n3", "1 2\n3 \n"));
+    }
+}
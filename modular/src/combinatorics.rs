@@ -0,0 +1,201 @@
+//! Counting modulo a prime with precomputed factorials, and exactly while it fits in `u128`.
+
+use crate::ModInt;
+
+/// Factorials and their inverses modulo the prime `M` of the integers up to a limit, answering
+/// binomial coefficients in constant time.
+pub struct Factorials<const M: u64> {
+    factorials: Vec<ModInt<M>>,
+    inverse_factorials: Vec<ModInt<M>>,
+}
+
+impl<const M: u64> Factorials<M> {
+    /// Computes the factorials of `0..=limit`, which have to be below `M` to be invertible.
+    pub fn new(limit: usize) -> Self {
+        debug_assert!((limit as u64) < M);
+        let mut factorials = Vec::with_capacity(limit + 1);
+        factorials.push(ModInt::new(1));
+        for number in 1..=limit {
+            factorials.push(factorials[number - 1] * ModInt::from(number));
+        }
+        let mut inverse_factorials = vec![ModInt::new(1); limit + 1];
+        inverse_factorials[limit] = factorials[limit]
+            .inverse()
+            .expect("the factorials below a prime are invertible");
+        for number in (1..=limit).rev() {
+            inverse_factorials[number - 1] = inverse_factorials[number] * ModInt::from(number);
+        }
+        Self {
+            factorials,
+            inverse_factorials,
+        }
+    }
+
+    /// Returns the largest number whose factorial is known.
+    pub fn limit(&self) -> usize {
+        self.factorials.len() - 1
+    }
+
+    pub fn factorial(&self, number: usize) -> ModInt<M> {
+        self.factorials[number]
+    }
+
+    pub fn inverse_factorial(&self, number: usize) -> ModInt<M> {
+        self.inverse_factorials[number]
+    }
+
+    /// Returns `n` choose `k`, zero when `k > n`.
+    pub fn binomial(&self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::new(0);
+        }
+        self.factorials[n] * self.inverse_factorials[k] * self.inverse_factorials[n - k]
+    }
+
+    /// Returns `n` choose `k` for any `n` by Lucas' theorem, multiplying the binomials of the
+    /// digits of `n` and `k` in base `M`. Needs the factorials up to `M - 1`.
+    pub fn lucas_binomial(&self, mut n: u64, mut k: u64) -> ModInt<M> {
+        debug_assert_eq!(self.limit() as u64, M - 1);
+        let mut result = ModInt::new(1);
+        while k > 0 {
+            result *= self.binomial((n % M) as usize, (k % M) as usize);
+            n /= M;
+            k /= M;
+        }
+        result
+    }
+
+    /// Returns the `n`-th Catalan number, `(2n choose n) / (n + 1)`. Needs the factorials up to
+    /// `2n`.
+    pub fn catalan(&self, n: usize) -> ModInt<M> {
+        self.factorials[2 * n] * self.inverse_factorials[n + 1] * self.inverse_factorials[n]
+    }
+}
+
+/// Returns `n!`, or `None` if it does not fit in `u128`.
+pub fn factorial(n: u64) -> Option<u128> {
+    (1..=u128::from(n)).try_fold(1u128, |product, number| product.checked_mul(number))
+}
+
+/// Returns `n` choose `k`, or `None` if it does not fit in `u128`.
+///
+/// The binomials `n choose i` for `i` up to `min(k, n - k)` grow, so computing them one from
+/// another overflows only if the result does. Every step multiplies by `n - i` and divides by
+/// `i + 1`, which is exact once their common factors with the previous binomial are cancelled.
+pub fn binomial(n: u64, k: u64) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut result = 1u128;
+    for i in 0..u128::from(k) {
+        let (factor, divisor) = (u128::from(n) - i, i + 1);
+        let common = gcd(result, divisor);
+        let divisor = divisor / common;
+        result = (result / common).checked_mul(factor / divisor)?;
+    }
+    Some(result)
+}
+
+fn gcd(mut first: u128, mut second: u128) -> u128 {
+    while second != 0 {
+        (first, second) = (second, first % second);
+    }
+    first
+}
+
+/// Returns the unsigned Stirling numbers of the first kind `c(n, k)` for `n, k <= limit`, counting
+/// the permutations of `n` elements with `k` cycles.
+pub fn stirling_first<const M: u64>(limit: usize) -> Vec<Vec<ModInt<M>>> {
+    stirling(limit, |n, _| ModInt::from(n - 1))
+}
+
+/// Returns the Stirling numbers of the second kind `S(n, k)` for `n, k <= limit`, counting the
+/// partitions of `n` elements into `k` non-empty sets.
+pub fn stirling_second<const M: u64>(limit: usize) -> Vec<Vec<ModInt<M>>> {
+    stirling(limit, |_, k| ModInt::from(k))
+}
+
+/// Fills the table of `s(n, k) = weight(n, k) s(n - 1, k) + s(n - 1, k - 1)`, the recurrence of
+/// both kinds, as the `n`-th element either joins an existing group or starts a new one.
+fn stirling<const M: u64>(
+    limit: usize,
+    weight: impl Fn(usize, usize) -> ModInt<M>,
+) -> Vec<Vec<ModInt<M>>> {
+    let mut table = vec![vec![ModInt::new(0); limit + 1]; limit + 1];
+    table[0][0] = ModInt::new(1);
+    for n in 1..=limit {
+        for k in 1..=n {
+            table[n][k] = weight(n, k) * table[n - 1][k] + table[n - 1][k - 1];
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Mod = ModInt<1_000_000_007>;
+
+    #[test]
+    fn test_factorials() {
+        let factorials = Factorials::<1_000_000_007>::new(100);
+        assert_eq!(Mod::new(3_628_800), factorials.factorial(10));
+        assert_eq!(
+            Mod::new(1),
+            factorials.factorial(7) * factorials.inverse_factorial(7)
+        );
+        assert_eq!(Mod::new(252), factorials.binomial(10, 5));
+        assert_eq!(Mod::new(0), factorials.binomial(5, 10));
+        assert_eq!(Mod::new(538_992_043), factorials.binomial(100, 50));
+        let catalan = (0..8).map(|n| factorials.catalan(n).value());
+        assert_eq!(
+            vec![1, 1, 2, 5, 14, 42, 132, 429],
+            catalan.collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_lucas_binomial() {
+        let factorials = Factorials::<13>::new(12);
+        for n in 0..120 {
+            for k in 0..=n {
+                let expected = binomial(n, k).unwrap() % 13;
+                assert_eq!(expected as u64, factorials.lucas_binomial(n, k).value());
+            }
+        }
+        let power = 13u64.pow(15);
+        assert_eq!(2, factorials.lucas_binomial(2 * power, power).value());
+        assert_eq!(0, factorials.lucas_binomial(power, power - 1).value());
+    }
+
+    #[test]
+    fn test_exact() {
+        assert_eq!(Some(1), factorial(0));
+        assert_eq!(Some(120), factorial(5));
+        assert!(factorial(34).is_some());
+        assert_eq!(None, factorial(35));
+
+        assert_eq!(Some(0), binomial(3, 4));
+        assert_eq!(Some(1), binomial(0, 0));
+        assert_eq!(Some(75_287_520), binomial(100, 5));
+        assert_eq!(Some(499_999_999_500_000_000), binomial(10u64.pow(9), 2));
+        assert_eq!(
+            Some(100_891_344_545_564_193_334_812_497_256),
+            binomial(100, 50)
+        );
+        assert_eq!(Some(u128::from(u64::MAX)), binomial(u64::MAX, 1));
+        assert_eq!(None, binomial(u64::MAX, 3));
+        assert_eq!(None, binomial(200, 100));
+    }
+
+    #[test]
+    fn test_stirling() {
+        let first = stirling_first::<1_000_000_007>(5);
+        assert_eq!([0, 24, 50, 35, 10, 1].map(Mod::new).as_slice(), first[5]);
+        let second = stirling_second::<1_000_000_007>(5);
+        assert_eq!([0, 1, 15, 25, 10, 1].map(Mod::new).as_slice(), second[5]);
+        assert_eq!(Mod::new(1), second[0][0]);
+    }
+}
@@ -0,0 +1,420 @@
+//! Arithmetic modulo an integer.
+//!
+//! [`ModInt`] fixes the modulus in its type, as problems asking for the answer modulo
+//! `10^9 + 7` do, while [`DynModInt`] carries a modulus read from the input. Both keep their values
+//! reduced, so the operators never need a `rem_euclid`, and find inverses with the extended
+//! Euclidean algorithm, which works for any modulus coprime with the value, prime or not. The
+//! [`combinatorics`] module counts with them: binomials from factorial tables, Catalan and Stirling
+//! numbers, along with exact binomials.
+//!
+//! ```
+//! use modular::ModInt;
+//!
+//! type Mod = ModInt<1_000_000_007>;
+//!
+//! let half = Mod::new(1) / Mod::new(2);
+//! assert_eq!(500_000_004, half.value());
+//! assert_eq!(Mod::new(1), half * Mod::new(2));
+//! assert_eq!(Mod::from(-1), Mod::new(1_000_000_006));
+//! // 1 + 3 + 9 + 27
+//! assert_eq!(40, Mod::new(3).geometric_sum(4).value());
+//! ```
+
+pub mod combinatorics;
+
+use std::fmt::{self, Display, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Integer modulo `M`, kept in `0..M`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    pub const MODULUS: u64 = M;
+
+    pub const fn new(value: u64) -> Self {
+        Self { value: value % M }
+    }
+
+    pub const fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        Self::new(arithmetic::pow(self.value, exponent, M))
+    }
+
+    /// Returns the inverse, if the value is coprime with the modulus.
+    pub fn inverse(self) -> Option<Self> {
+        arithmetic::inverse(self.value, M).map(Self::new)
+    }
+
+    /// Returns `1 + self + self^2 + ... + self^(terms - 1)`, without dividing by `self - 1`,
+    /// which may not be invertible.
+    pub fn geometric_sum(self, terms: u64) -> Self {
+        Self::new(arithmetic::geometric_sum(self.value, terms, M))
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+macro_rules! impl_from {
+    ($($unsigned:ty),* ; $($signed:ty),*) => {
+        $(
+            impl<const M: u64> From<$unsigned> for ModInt<M> {
+                fn from(value: $unsigned) -> Self {
+                    Self::new((value as u128 % M as u128) as u64)
+                }
+            }
+        )*
+        $(
+            impl<const M: u64> From<$signed> for ModInt<M> {
+                fn from(value: $signed) -> Self {
+                    Self::new((value as i128).rem_euclid(M as i128) as u64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(u8, u16, u32, u64, usize; i8, i16, i32, i64, isize);
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            value: arithmetic::add(self.value, other.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            value: arithmetic::sub(self.value, other.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            value: arithmetic::mul(self.value, other.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    /// Multiplies by the inverse of `other`, panicking if there is none.
+    fn div(self, other: Self) -> Self {
+        let inverse = other
+            .inverse()
+            .expect("the divisor is not coprime with the modulus");
+        self.mul(inverse)
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::default() - self
+    }
+}
+
+macro_rules! impl_assign {
+    ($($trait:ident $method:ident $operator:tt),*) => {
+        $(
+            impl<const M: u64> $trait for ModInt<M> {
+                fn $method(&mut self, other: Self) {
+                    *self = *self $operator other;
+                }
+            }
+        )*
+    };
+}
+
+impl_assign!(AddAssign add_assign +, SubAssign sub_assign -, MulAssign mul_assign *, DivAssign div_assign /);
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(0), Add::add)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), Mul::mul)
+    }
+}
+
+/// Integer modulo a modulus known at runtime, kept in `0..modulus`. Both operands of an operation
+/// have to share the modulus.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DynModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl DynModInt {
+    pub fn new(value: u64, modulus: u64) -> Self {
+        debug_assert!(modulus >= 1);
+        Self {
+            value: value % modulus,
+            modulus,
+        }
+    }
+
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn modulus(self) -> u64 {
+        self.modulus
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        self.with_value(arithmetic::pow(self.value, exponent, self.modulus))
+    }
+
+    /// Returns the inverse, if the value is coprime with the modulus.
+    pub fn inverse(self) -> Option<Self> {
+        arithmetic::inverse(self.value, self.modulus).map(|inverse| self.with_value(inverse))
+    }
+
+    /// Returns `1 + self + self^2 + ... + self^(terms - 1)`.
+    pub fn geometric_sum(self, terms: u64) -> Self {
+        self.with_value(arithmetic::geometric_sum(self.value, terms, self.modulus))
+    }
+
+    fn with_value(self, value: u64) -> Self {
+        Self::new(value, self.modulus)
+    }
+
+    fn common_modulus(self, other: Self) -> u64 {
+        debug_assert_eq!(self.modulus, other.modulus);
+        self.modulus
+    }
+}
+
+impl Display for DynModInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl Add for DynModInt {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let modulus = self.common_modulus(other);
+        self.with_value(arithmetic::add(self.value, other.value, modulus))
+    }
+}
+
+impl Sub for DynModInt {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let modulus = self.common_modulus(other);
+        self.with_value(arithmetic::sub(self.value, other.value, modulus))
+    }
+}
+
+impl Mul for DynModInt {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let modulus = self.common_modulus(other);
+        self.with_value(arithmetic::mul(self.value, other.value, modulus))
+    }
+}
+
+impl Div for DynModInt {
+    type Output = Self;
+
+    /// Multiplies by the inverse of `other`, panicking if there is none.
+    fn div(self, other: Self) -> Self {
+        let inverse = other
+            .inverse()
+            .expect("the divisor is not coprime with the modulus");
+        self.mul(inverse)
+    }
+}
+
+impl Neg for DynModInt {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.with_value(0) - self
+    }
+}
+
+macro_rules! impl_dyn_assign {
+    ($($trait:ident $method:ident $operator:tt),*) => {
+        $(
+            impl $trait for DynModInt {
+                fn $method(&mut self, other: Self) {
+                    *self = *self $operator other;
+                }
+            }
+        )*
+    };
+}
+
+impl_dyn_assign!(AddAssign add_assign +, SubAssign sub_assign -, MulAssign mul_assign *, DivAssign div_assign /);
+
+/// Operations on values reduced modulo any modulus, shared by both types.
+mod arithmetic {
+    pub fn add(first: u64, second: u64, modulus: u64) -> u64 {
+        let (sum, overflow) = first.overflowing_add(second);
+        if overflow || sum >= modulus {
+            sum.wrapping_sub(modulus)
+        } else {
+            sum
+        }
+    }
+
+    pub fn sub(first: u64, second: u64, modulus: u64) -> u64 {
+        if first >= second {
+            first - second
+        } else {
+            first.wrapping_sub(second).wrapping_add(modulus)
+        }
+    }
+
+    pub fn mul(first: u64, second: u64, modulus: u64) -> u64 {
+        (u128::from(first) * u128::from(second) % u128::from(modulus)) as u64
+    }
+
+    pub fn pow(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+        let mut result = 1 % modulus;
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = mul(result, base, modulus);
+            }
+            base = mul(base, base, modulus);
+            exponent /= 2;
+        }
+        result
+    }
+
+    /// Finds `x` with `value * x + modulus * y = gcd(value, modulus)` by the extended Euclidean
+    /// algorithm, which is the inverse when the gcd is one.
+    pub fn inverse(value: u64, modulus: u64) -> Option<u64> {
+        let (mut remainder, mut next_remainder) = (i128::from(modulus), i128::from(value));
+        let (mut coefficient, mut next_coefficient) = (0i128, 1i128);
+        while next_remainder != 0 {
+            let quotient = remainder / next_remainder;
+            (remainder, next_remainder) = (next_remainder, remainder - quotient * next_remainder);
+            (coefficient, next_coefficient) =
+                (next_coefficient, coefficient - quotient * next_coefficient);
+        }
+        (remainder == 1).then(|| coefficient.rem_euclid(i128::from(modulus)) as u64)
+    }
+
+    /// Builds the sum of the first `terms` powers along the bits of `terms` from the highest,
+    /// doubling the number of terms with `S(2n) = S(n) (1 + r^n)` and adding one with
+    /// `S(n + 1) = S(n) + r^n`.
+    pub fn geometric_sum(ratio: u64, terms: u64, modulus: u64) -> u64 {
+        let (mut sum, mut power) = (0, 1 % modulus);
+        for bit in (0..u64::BITS - terms.leading_zeros()).rev() {
+            sum = mul(sum, add(1, power, modulus), modulus);
+            power = mul(power, power, modulus);
+            if terms >> bit & 1 == 1 {
+                sum = add(sum, power, modulus);
+                power = mul(power, ratio, modulus);
+            }
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Mod = ModInt<1_000_000_009>;
+
+    #[test]
+    fn test_operators() {
+        let (first, second) = (Mod::new(1_000_000_000), Mod::new(10));
+        assert_eq!(1, (first + second).value());
+        assert_eq!(999_999_990, (first - second).value());
+        assert_eq!(1_000_000_009 - 90, (first * second).value());
+        assert_eq!(Mod::new(9), -first);
+        assert_eq!(Mod::new(0), -Mod::new(0));
+        assert_eq!(first, first * second / second);
+        assert_eq!(Mod::from(-10i64), -second);
+        assert_eq!(Mod::new(1), Mod::from(2_000_000_019u64));
+        assert_eq!(15, (1..=5).map(Mod::new).sum::<Mod>().value());
+        assert_eq!(120, (1..=5).map(Mod::new).product::<Mod>().value());
+
+        let mut value = first;
+        value += second;
+        value *= second;
+        value -= Mod::new(5);
+        value /= Mod::new(5);
+        assert_eq!(1, value.value());
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        const LARGE: u64 = u64::MAX - 58;
+        let value = ModInt::<LARGE>::new(LARGE - 1);
+        assert_eq!(LARGE - 2, (value + value).value());
+        assert_eq!(1, (value * value).value());
+        assert_eq!(1, (ModInt::<LARGE>::new(0) - value).value());
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(Some(Mod::new(149163232)), Mod::new(4123).inverse());
+        assert_eq!(None, Mod::new(0).inverse());
+        assert_eq!(Some(ModInt::<10>::new(7)), ModInt::<10>::new(3).inverse());
+        assert_eq!(None, ModInt::<10>::new(4).inverse());
+        assert_eq!(Some(DynModInt::new(5, 12)), DynModInt::new(5, 12).inverse());
+    }
+
+    #[test]
+    fn test_geometric_sum() {
+        for modulus in [1, 2, 12, 1_000_000_009] {
+            for ratio in 0..5 {
+                let mut expected = 0;
+                let mut power = 1 % modulus;
+                for terms in 0..40 {
+                    let ratio = DynModInt::new(ratio, modulus);
+                    assert_eq!(expected, ratio.geometric_sum(terms).value());
+                    expected = (expected + power) % modulus;
+                    power = power * ratio.value() % modulus;
+                }
+            }
+        }
+        assert_eq!(Mod::new(82), Mod::new(1).geometric_sum(10u64.pow(18) + 1));
+    }
+
+    #[test]
+    fn test_dyn_mod_int() {
+        let (first, second) = (DynModInt::new(25, 30), DynModInt::new(10, 30));
+        assert_eq!(5, (first + second).value());
+        assert_eq!(15, (first - second).value());
+        assert_eq!(10, (first * second).value());
+        assert_eq!(5, (-first).value());
+        assert_eq!(1, DynModInt::new(7, 30).pow(4).value());
+        assert_eq!(0, DynModInt::new(7, 1).pow(0).value());
+    }
+}
@@ -0,0 +1,257 @@
+//! Primes, factorizations and divisors of `u64` integers.
+//!
+//! Single numbers are tested with a deterministic Miller–Rabin test and factorized with Pollard's
+//! rho algorithm, in time polynomial in the number of their digits, while a [`Sieve`] finds the
+//! smallest prime factors of all numbers up to a limit at once, for problems factorizing many
+//! small numbers. Factorizations are lists of primes with their exponents, from which
+//! [`divisors`] enumerates all divisors.
+//!
+//! ```
+//! use number_theory::{divisors, factorize, is_prime, split_twos};
+//!
+//! assert!(is_prime(1_000_000_007));
+//! assert_eq!(vec![(2, 2), (3, 1), (1_000_000_007, 1)], factorize(12_000_000_084));
+//! assert_eq!(vec![1, 2, 3, 4, 6, 12], divisors(&factorize(12)));
+//! assert_eq!((3, 5), split_twos(40));
+//! ```
+
+mod sieve;
+
+pub use sieve::Sieve;
+
+/// Primes with their exponents, in increasing order of the primes.
+pub type Factorization = Vec<(u64, u32)>;
+
+/// Witnesses deciding the primality of every `u64` in the Miller–Rabin test.
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Returns whether the number is prime, by the Miller–Rabin test with bases that are known to leave
+/// no composite `u64` undetected.
+pub fn is_prime(number: u64) -> bool {
+    if number < 2 {
+        return false;
+    }
+    if let Some(&prime) = WITNESSES.iter().find(|&&prime| number % prime == 0) {
+        return number == prime;
+    }
+    let (twos, odd) = split_twos(number - 1);
+    WITNESSES.iter().all(|&witness| {
+        let mut power = pow(witness, odd, number);
+        if power == 1 || power == number - 1 {
+            return true;
+        }
+        for _ in 1..twos {
+            power = mul(power, power, number);
+            if power == number - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Returns the prime factorization of the number, which has to be positive.
+pub fn factorize(number: u64) -> Factorization {
+    debug_assert!(number > 0);
+    let (twos, odd) = split_twos(number);
+    let mut primes = vec![2; twos as usize];
+    let mut pending = vec![odd];
+    while let Some(number) = pending.pop() {
+        if number == 1 {
+            continue;
+        }
+        if is_prime(number) {
+            primes.push(number);
+            continue;
+        }
+        let factor = (1..)
+            .find_map(|increment| find_factor(number, increment))
+            .expect("some increment finds a factor of a composite number");
+        pending.extend([factor, number / factor]);
+    }
+    primes.sort_unstable();
+    group(primes)
+}
+
+/// Looks for a nontrivial factor of an odd composite number by Pollard's rho algorithm in Brent's
+/// variant, walking the sequence `x -> x^2 + increment` until two of its elements meet modulo some
+/// factor. The differences are multiplied in batches to take fewer gcds, going back to the single
+/// differences of the last batch if its product is the whole number.
+fn find_factor(number: u64, increment: u64) -> Option<u64> {
+    const BATCH: u64 = 128;
+    let step = |x: u64| add(mul(x, x, number), increment, number);
+    let (mut fast, mut product, mut divisor) = (2, 1, 1);
+    let (mut slow, mut batch_start) = (fast, fast);
+    let mut length = 1;
+    while divisor == 1 {
+        slow = fast;
+        for _ in 0..length {
+            fast = step(fast);
+        }
+        let mut walked = 0;
+        while walked < length && divisor == 1 {
+            batch_start = fast;
+            for _ in 0..BATCH.min(length - walked) {
+                fast = step(fast);
+                product = mul(product, slow.abs_diff(fast), number);
+            }
+            divisor = gcd(product, number);
+            walked += BATCH;
+        }
+        length *= 2;
+    }
+    if divisor == number {
+        loop {
+            batch_start = step(batch_start);
+            divisor = gcd(slow.abs_diff(batch_start), number);
+            if divisor > 1 {
+                break;
+            }
+        }
+    }
+    (divisor != number).then_some(divisor)
+}
+
+/// Groups equal primes of a sorted list into powers.
+fn group(primes: Vec<u64>) -> Factorization {
+    let mut factorization: Factorization = Vec::new();
+    for prime in primes {
+        match factorization.last_mut() {
+            Some((last, exponent)) if *last == prime => *exponent += 1,
+            _ => factorization.push((prime, 1)),
+        }
+    }
+    factorization
+}
+
+/// Returns all divisors of the number with the factorization, in increasing order.
+pub fn divisors(factorization: &[(u64, u32)]) -> Vec<u64> {
+    let mut divisors = vec![1];
+    for &(prime, exponent) in factorization {
+        let previous = divisors.len();
+        let mut power = 1;
+        for _ in 0..exponent {
+            power *= prime;
+            for index in 0..previous {
+                divisors.push(divisors[index] * power);
+            }
+        }
+    }
+    divisors.sort_unstable();
+    divisors
+}
+
+/// Splits the positive number into the exponent of two in it and the remaining odd part.
+pub fn split_twos(number: u64) -> (u32, u64) {
+    debug_assert!(number > 0);
+    let twos = number.trailing_zeros();
+    (twos, number >> twos)
+}
+
+pub fn gcd(mut first: u64, mut second: u64) -> u64 {
+    while second != 0 {
+        (first, second) = (second, first % second);
+    }
+    first
+}
+
+fn add(first: u64, second: u64, modulus: u64) -> u64 {
+    ((u128::from(first) + u128::from(second)) % u128::from(modulus)) as u64
+}
+
+fn mul(first: u64, second: u64, modulus: u64) -> u64 {
+    (u128::from(first) * u128::from(second) % u128::from(modulus)) as u64
+}
+
+fn pow(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1;
+    base %= modulus;
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            result = mul(result, base, modulus);
+        }
+        base = mul(base, base, modulus);
+        exponent /= 2;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_prime_by_trial_division(number: u64) -> bool {
+        number >= 2
+            && (2..)
+                .take_while(|divisor| divisor * divisor <= number)
+                .all(|divisor| number % divisor != 0)
+    }
+
+    #[test]
+    fn test_is_prime() {
+        for number in 0..10_000 {
+            assert_eq!(
+                is_prime_by_trial_division(number),
+                is_prime(number),
+                "{number}"
+            );
+        }
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(u64::MAX));
+        // Strong pseudoprimes to the first bases.
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(3_825_123_056_546_413_051));
+        assert!(!is_prime(4_294_967_297));
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(Factorization::new(), factorize(1));
+        assert_eq!(vec![(2, 63)], factorize(1 << 63));
+        assert_eq!(vec![(3, 2), (5, 1)], factorize(45));
+        assert_eq!(vec![(641, 1), (6_700_417, 1)], factorize(4_294_967_297));
+        assert_eq!(
+            vec![(4_294_967_279, 1), (4_294_967_291, 1)],
+            factorize(4_294_967_279 * 4_294_967_291)
+        );
+        assert_eq!(
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65_537, 1),
+                (6_700_417, 1)
+            ],
+            factorize(u64::MAX)
+        );
+        assert_eq!(vec![(999_999_937, 2)], factorize(999_999_937 * 999_999_937));
+        for number in 1..3_000 {
+            let product = factorize(number)
+                .iter()
+                .map(|&(prime, exponent)| prime.pow(exponent))
+                .product::<u64>();
+            assert_eq!(number, product);
+        }
+    }
+
+    #[test]
+    fn test_divisors() {
+        assert_eq!(vec![1], divisors(&factorize(1)));
+        assert_eq!(
+            vec![1, 2, 4, 5, 10, 20, 25, 50, 100],
+            divisors(&factorize(100))
+        );
+        assert_eq!(6_720, divisors(&factorize(963_761_198_400)).len());
+    }
+
+    #[test]
+    fn test_split_twos() {
+        assert_eq!((0, 1), split_twos(1));
+        assert_eq!((0, 15), split_twos(15));
+        assert_eq!((4, 3), split_twos(48));
+        assert_eq!((63, 1), split_twos(1 << 63));
+    }
+}
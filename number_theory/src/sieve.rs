@@ -0,0 +1,86 @@
+use crate::{group, Factorization};
+
+/// Smallest prime factors of the numbers up to a limit, found by the linear sieve, which marks
+/// every composite number once, as its smallest prime factor times a number whose smallest prime
+/// factor is not smaller.
+pub struct Sieve {
+    /// Smallest prime factor of every number, zero for zero and one.
+    smallest_prime_factors: Vec<u32>,
+    primes: Vec<u32>,
+}
+
+impl Sieve {
+    pub fn new(limit: u32) -> Self {
+        let mut smallest_prime_factors = vec![0; limit as usize + 1];
+        let mut primes = Vec::new();
+        for number in 2..=limit {
+            if smallest_prime_factors[number as usize] == 0 {
+                smallest_prime_factors[number as usize] = number;
+                primes.push(number);
+            }
+            let smallest = smallest_prime_factors[number as usize];
+            for &prime in primes.iter().take_while(|&&prime| prime <= smallest) {
+                let multiple = u64::from(number) * u64::from(prime);
+                if multiple > u64::from(limit) {
+                    break;
+                }
+                smallest_prime_factors[multiple as usize] = prime;
+            }
+        }
+        Self {
+            smallest_prime_factors,
+            primes,
+        }
+    }
+
+    /// Returns the largest number the sieve knows.
+    pub fn limit(&self) -> u32 {
+        (self.smallest_prime_factors.len() - 1) as u32
+    }
+
+    /// Returns the primes up to the limit in increasing order.
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    pub fn is_prime(&self, number: u32) -> bool {
+        number >= 2 && self.smallest_prime_factor(number) == number
+    }
+
+    pub fn smallest_prime_factor(&self, number: u32) -> u32 {
+        debug_assert!((2..=self.limit()).contains(&number));
+        self.smallest_prime_factors[number as usize]
+    }
+
+    /// Returns the prime factorization of a positive number up to the limit, dividing it by its
+    /// smallest prime factors.
+    pub fn factorize(&self, mut number: u32) -> Factorization {
+        debug_assert!((1..=self.limit()).contains(&number));
+        let mut primes = Vec::new();
+        while number > 1 {
+            let prime = self.smallest_prime_factor(number);
+            primes.push(u64::from(prime));
+            number /= prime;
+        }
+        group(primes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{factorize, is_prime};
+
+    #[test]
+    fn test_sieve() {
+        let sieve = Sieve::new(10_000);
+        assert_eq!(&[2, 3, 5, 7, 11, 13], &sieve.primes()[..6]);
+        assert_eq!(1_229, sieve.primes().len());
+        for number in 1..=10_000 {
+            assert_eq!(is_prime(u64::from(number)), sieve.is_prime(number));
+            assert_eq!(factorize(u64::from(number)), sieve.factorize(number));
+        }
+        assert_eq!(97, sieve.smallest_prime_factor(97 * 101));
+        assert!(Sieve::new(1).primes().is_empty());
+    }
+}
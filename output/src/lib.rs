@@ -0,0 +1,226 @@
+//! Buffered writing of problem answers.
+//!
+//! [`Output`] wraps a writer, usually the standard output, into a [`BufWriter`], so that answers
+//! written line by line are not flushed one line at a time. The remaining types format answers
+//! through [`Display`], in the shapes Codeforces problems ask for:
+//!
+//! * [`Words`] and [`Lines`] join a sequence with spaces or line breaks;
+//! * [`Verdict`] writes a boolean as `YES`/`NO` or any other [`Spelling`];
+//! * [`OrMinusOne`] and [`OrNo`] write an optional answer;
+//! * [`Fixed`] writes a float with a fixed number of decimal places.
+//!
+//! ```
+//! use output::{Fixed, Output, Verdict, Words};
+//!
+//! let mut buffer = Vec::new();
+//! let mut output = Output::new(&mut buffer);
+//! output.line(Verdict::yes_no(true)).unwrap();
+//! output.line(Words(vec![1, 2, 3])).unwrap();
+//! output.line(Fixed(0.5, 3)).unwrap();
+//! output.flush().unwrap();
+//! drop(output);
+//! assert_eq!(b"YES\n1 2 3\n0.500\n", buffer.as_slice());
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, BufWriter, StdoutLock, Write};
+
+/// Buffered writer of answers, flushed when dropped or by [`Output::flush`].
+pub struct Output<W: Write> {
+    writer: BufWriter<W>,
+}
+
+impl Output<StdoutLock<'static>> {
+    /// Locks the standard output for the lifetime of the writer.
+    pub fn stdout() -> Self {
+        Self::new(io::stdout().lock())
+    }
+}
+
+impl<W: Write> Output<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: BufWriter::new(writer),
+        }
+    }
+
+    /// Writes the value followed by a line break.
+    pub fn line(&mut self, value: impl Display) -> io::Result<()> {
+        writeln!(self.writer, "{value}")
+    }
+
+    /// Writes the items on a single line, separated by spaces.
+    pub fn words<T: Display>(&mut self, items: impl IntoIterator<Item = T>) -> io::Result<()> {
+        for (index, item) in items.into_iter().enumerate() {
+            if index > 0 {
+                self.writer.write_all(b" ")?;
+            }
+            write!(self.writer, "{item}")?;
+        }
+        self.writer.write_all(b"\n")
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Answers written on a single line, separated by spaces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Words<T>(pub Vec<T>);
+
+impl<T: Display> Display for Words<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_separated(f, &self.0, " ")
+    }
+}
+
+/// Answers written one per line, such as those of independent test cases.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lines<T>(pub Vec<T>);
+
+impl<T: Display> Display for Lines<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_separated(f, &self.0, "\n")
+    }
+}
+
+fn write_separated<T: Display>(f: &mut Formatter<'_>, items: &[T], separator: &str) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            f.write_str(separator)?;
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
+/// How the two values of a [`Verdict`] are written.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Spelling {
+    pub yes: &'static str,
+    pub no: &'static str,
+}
+
+impl Spelling {
+    pub const YES_NO: Spelling = Spelling::new("YES", "NO");
+
+    pub const fn new(yes: &'static str, no: &'static str) -> Self {
+        Self { yes, no }
+    }
+}
+
+/// Answer to a decision problem, such as `Verdict::new(wins, Spelling::new("First", "Second"))`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Verdict {
+    pub value: bool,
+    pub spelling: Spelling,
+}
+
+impl Verdict {
+    pub const fn new(value: bool, spelling: Spelling) -> Self {
+        Self { value, spelling }
+    }
+
+    pub const fn yes_no(value: bool) -> Self {
+        Self::new(value, Spelling::YES_NO)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Spelling { yes, no } = self.spelling;
+        f.write_str(if self.value { yes } else { no })
+    }
+}
+
+/// Optional answer written as itself, or as `-1` when there is none.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OrMinusOne<T>(pub Option<T>);
+
+impl<T: Display> Display for OrMinusOne<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(answer) => write!(f, "{answer}"),
+            None => f.write_str("-1"),
+        }
+    }
+}
+
+/// Optional answer written as `YES` followed by the answer on the next line, or as `NO` when there
+/// is none.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OrNo<T>(pub Option<T>);
+
+impl<T: Display> Display for OrNo<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Spelling { yes, no } = Spelling::YES_NO;
+        match &self.0 {
+            Some(answer) => write!(f, "{yes}\n{answer}"),
+            None => f.write_str(no),
+        }
+    }
+}
+
+/// Float written with the given number of decimal places.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fixed(pub f64, pub usize);
+
+impl Display for Fixed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.*}", self.1, self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output() -> io::Result<()> {
+        let mut buffer = Vec::new();
+        let mut output = Output::new(&mut buffer);
+        output.line(1)?;
+        output.words([2, 3, 4])?;
+        output.words(Vec::<u8>::new())?;
+        output.line(Lines(vec!["a", "b"]))?;
+        drop(output);
+        assert_eq!("1\n2 3 4\n\na\nb\n", String::from_utf8_lossy(&buffer));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sequences() {
+        assert_eq!("1 2 3", Words(vec![1, 2, 3]).to_string());
+        assert_eq!("", Words(Vec::<u8>::new()).to_string());
+        assert_eq!("1\n2\n3", Lines(vec![1, 2, 3]).to_string());
+        assert_eq!(
+            "1 2\n3",
+            Lines(vec![Words(vec![1, 2]), Words(vec![3])]).to_string()
+        );
+    }
+
+    #[test]
+    fn test_verdicts() {
+        const FIRST_SECOND: Spelling = Spelling::new("First", "Second");
+        assert_eq!("YES", Verdict::yes_no(true).to_string());
+        assert_eq!("NO", Verdict::yes_no(false).to_string());
+        assert_eq!("First", Verdict::new(true, FIRST_SECOND).to_string());
+        assert_eq!("Second", Verdict::new(false, FIRST_SECOND).to_string());
+    }
+
+    #[test]
+    fn test_optional_answers() {
+        assert_eq!("5", OrMinusOne(Some(5)).to_string());
+        assert_eq!("-1", OrMinusOne(None::<u8>).to_string());
+        assert_eq!("YES\nabc", OrNo(Some("abc")).to_string());
+        assert_eq!("NO", OrNo(None::<u8>).to_string());
+    }
+
+    #[test]
+    fn test_fixed() {
+        assert_eq!("0.33", Fixed(1.0 / 3.0, 2).to_string());
+        assert_eq!("2", Fixed(1.5, 0).to_string());
+        assert_eq!("-4.500000", Fixed(-4.5, 6).to_string());
+    }
+}
@@ -0,0 +1,189 @@
+//! The `parse_next!` macro declaring variables parsed from the next lines of a
+//! [`scanner::Scanner`].
+//!
+//! Every arm declares the parsed variables in the calling scope and uses `?` to return malformed
+//! input as a [`scanner::ScanError`], so the caller has to return a `Result` whose error converts
+//! from it. Constraint ranges and lengths are checked by the scanner, see [`scanner::Validation`].
+//! The expansion refers to the `scanner` crate by name and calls [`scanner::Scanner::next_line`] as
+//! a method, so the calling crate has to depend on `scanner` and import the `Scanner` trait.
+//!
+//! ```
+//! use parse_next::parse_next;
+//! use scanner::{Scanner, StringScanner};
+//!
+//! fn main() -> scanner::Result<()> {
+//!     let mut scanner = StringScanner::from("2 3\n4 5\n");
+//!     parse_next!(scanner => let x: u8 = 1..=10; let y: u8 = 1..=10);
+//!     parse_next!(scanner => let items: Vec<u8> = [1..=10; 2]);
+//!     assert_eq!((2, 3, vec![4, 5]), (x, y, items));
+//!     scanner.finish()
+//! }
+//! ```
+
+/// Parses the next input line into variables.
+///
+/// * `scanner => let x: T = range; let y: U = range` parses items of a single line;
+/// * `scanner => let items: Vec<T> = [range; count]` parses a line of `count` items;
+/// * `scanner => let pairs: Vec<(T, U)> = [(range, range); count]` parses `count` lines, each
+///   into a tuple;
+/// * `scanner => let grid: Vec<Vec<char>> = [rows; columns]` reads `rows` lines of `columns`
+///   characters;
+/// * `scanner => let text: String = [len range]` reads a line of a single word, whose length lies
+///   in the range;
+/// * `line -> let x: T = range; ...` parses items of an already read line.
+#[macro_export]
+macro_rules! parse_next {
+    ($scanner:expr => let $name:ident: Vec<($($ty:ty),+)> = [($($range:expr),+); $count:expr]) => {
+        let mut $name = ::std::vec::Vec::with_capacity($count as usize);
+        for _ in 0..$count {
+            let mut line = $scanner.next_line()?;
+            let item: ($($ty,)+) = scanner::LineScanner::next_tuple(&mut line, ($($range,)+))?;
+            $name.push(item);
+        }
+    };
+    ($scanner:expr => let $name:ident: Vec<Vec<char>> = [$rows:expr; $columns:expr]) => {
+        let mut $name = ::std::vec::Vec::with_capacity($rows as usize);
+        for _ in 0..$rows {
+            let mut line = $scanner.next_line()?;
+            let row = scanner::LineScanner::next_checked(&mut line, |row: &::std::string::String| {
+                row.chars().count() == $columns as usize
+            })?;
+            $name.push(row.chars().collect::<::std::vec::Vec<char>>());
+        }
+    };
+    ($scanner:expr => let $name:ident: String = [len $range:expr]) => {
+        let $name: ::std::string::String = {
+            let mut line = $scanner.next_line()?;
+            scanner::LineScanner::next_checked(&mut line, |text: &::std::string::String| {
+                ($range).contains(&text.len())
+            })?
+        };
+    };
+    ($scanner:expr => let $name:ident: $ty:ty = [$range:expr; $count:expr]) => {
+        let $name: $ty = {
+            let mut line = $scanner.next_line()?;
+            scanner::LineScanner::next_vec(&mut line, $count as usize, $range)?
+        };
+    };
+    ($scanner:expr => $(let $name:ident: $ty:ty = $range:expr);+) => {
+        let ($($name,)+) = {
+            let mut line = $scanner.next_line()?;
+            $(
+                let $name: $ty = scanner::LineScanner::next_ranged(&mut line, $range)?;
+            )+
+            ($($name,)+)
+        };
+    };
+    ($line:ident -> $(let $name:ident: $ty:ty = $range:expr);+) => {
+        $(
+            let $name: $ty = scanner::LineScanner::next_ranged(&mut $line, $range)?;
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use scanner::{Result, Scanner, StringScanner};
+
+    #[test]
+    fn test_single_line() -> Result<()> {
+        let mut scanner = StringScanner::from("1 -2 x\n");
+        parse_next!(scanner => let a: u8 = 1..=1; let b: i32 = -2..0; let c: char = 'w'..='z');
+        assert_eq!((1, -2, 'x'), (a, b, c));
+        scanner.finish()
+    }
+
+    #[test]
+    fn test_read_line() -> Result<()> {
+        let mut scanner = StringScanner::from("3 4\n");
+        let mut line = scanner.next_line()?;
+        parse_next!(line -> let a: u8 = 1..=3; let b: u64 = 4..=4);
+        assert_eq!((3, 4), (a, b));
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector() -> Result<()> {
+        let mut scanner = StringScanner::from("3\n5 6 7\n");
+        parse_next!(scanner => let count: usize = 1..=3);
+        parse_next!(scanner => let items: Vec<u16> = [5..=7; count]);
+        assert_eq!(vec![5, 6, 7], items);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tuple_lines() -> Result<()> {
+        let mut scanner = StringScanner::from("1 a\n2 b\n3 c\n");
+        parse_next!(scanner => let pairs: Vec<(u8, char)> = [(1..=3, 'a'..='c'); 2]);
+        assert_eq!(vec![(1, 'a'), (2, 'b')], pairs);
+        parse_next!(scanner => let singles: Vec<(u8, char)> = [(1..=3, 'a'..='c'); 1u32]);
+        assert_eq!(vec![(3, 'c')], singles);
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid() -> Result<()> {
+        let mut scanner = StringScanner::from("#.\n.#\n..\n");
+        parse_next!(scanner => let grid: Vec<Vec<char>> = [3; 2]);
+        assert_eq!(vec![vec!['#', '.'], vec!['.', '#'], vec!['.', '.']], grid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_line() -> Result<()> {
+        let mut scanner = StringScanner::from("word\n");
+        parse_next!(scanner => let text: String = [len 1..=9]);
+        assert_eq!("word", text);
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict_lengths() {
+        fn grid(input: &str) -> Result<Vec<Vec<char>>> {
+            let mut scanner = StringScanner::from(input).validating();
+            parse_next!(scanner => let grid: Vec<Vec<char>> = [2; 2]);
+            Ok(grid)
+        }
+
+        fn text(input: &str) -> Result<String> {
+            let mut scanner = StringScanner::from(input).validating();
+            parse_next!(scanner => let text: String = [len 1..=3]);
+            Ok(text)
+        }
+
+        let error = grid("#.\n.#.\n").unwrap_err();
+        assert_eq!("line 2, token 1: `.#.` is out of range", error.to_string());
+        let error = text("abcd\n").unwrap_err();
+        assert_eq!("line 1, token 1: `abcd` is out of range", error.to_string());
+        assert_eq!("abc", text("abc\n").unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        fn parse(input: &str) -> Result<(u8, u8)> {
+            let mut scanner = StringScanner::from(input);
+            parse_next!(scanner => let first: u8 = 0..; let second: u8 = 0..);
+            Ok((first, second))
+        }
+
+        assert_eq!("line 1: missing line", parse("").unwrap_err().to_string());
+        assert_eq!(
+            "line 1, token 2: missing token",
+            parse("1").unwrap_err().to_string()
+        );
+        assert!(parse("1 x").is_err());
+        assert_eq!((1, 2), parse("1 2").unwrap());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_out_of_range() {
+        let parse = || -> Result<u8> {
+            let mut scanner = StringScanner::from("11");
+            parse_next!(scanner => let value: u8 = 1..=10);
+            Ok(value)
+        };
+        let _ = parse();
+    }
+}
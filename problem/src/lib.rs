@@ -0,0 +1,207 @@
+//! The shape shared by all problem solutions: input read with a [`Scanner`], a solver and an
+//! answer written through [`Display`], usually with the helpers of the `output` crate.
+//!
+//! A solution implements [`Problem`] and its `main` is just `problem::run::<Solution>()`, or
+//! implements [`TestCase`] and runs as `problem::run::<TestCases<Solution>>()` when the input
+//! holds several independent cases. Tests and tools drive a solution the same way through
+//! [`run_with`], giving it any scanner and writer.
+//!
+//! ```
+//! use problem::Problem;
+//! use scanner::{LineScanner, Scan, Scanner, StringScanner};
+//!
+//! struct Sum(Vec<u32>);
+//!
+//! impl Scan for Sum {
+//!     fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+//!         Ok(Sum(scanner.next_line()?.rest(..)?))
+//!     }
+//! }
+//!
+//! impl Problem for Sum {
+//!     type Input = Self;
+//!     type Output = u32;
+//!
+//!     fn solve(input: Self) -> problem::Result<u32> {
+//!         Ok(input.0.into_iter().sum())
+//!     }
+//! }
+//!
+//! let mut output = Vec::new();
+//! problem::run_with::<Sum>(StringScanner::from("1 2 3\n"), &mut output).unwrap();
+//! assert_eq!(b"6\n", output.as_slice());
+//! ```
+
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+use output::{Lines, Output};
+use scanner::{LineScanner, Scan, Scanner, StdinScanner};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+pub type Error = Box<dyn std::error::Error>;
+
+/// A problem solution.
+pub trait Problem {
+    /// The whole problem input.
+    type Input: Scan;
+    /// The answer, whose [`Display`] implementation writes it in the expected output format,
+    /// without the final line break.
+    type Output: Display;
+
+    fn solve(input: Self::Input) -> Result<Self::Output>;
+}
+
+/// Solution of a single test case, for problems whose input is the number of independent test
+/// cases followed by the cases themselves. Such problems are solved as [`TestCases`].
+pub trait TestCase {
+    /// Input of one test case.
+    type Input: Scan;
+    /// Answer to one test case, the answers are written one per line.
+    type Output: Display;
+    /// Constraint on the number of test cases.
+    const COUNT: RangeInclusive<u32>;
+
+    fn solve(input: Self::Input) -> Result<Self::Output>;
+}
+
+/// Problem consisting of test cases solved by `C`, read after the line with their count.
+///
+/// Each case is scanned by its own [`Scan`] implementation from where the previous one stopped.
+/// A missing case fails to scan, and input left after the last one is caught by the final
+/// [`Scanner::finish`] check of [`run_with`], so exactly the announced number of cases is read.
+pub struct TestCases<C: TestCase>(pub Vec<C::Input>);
+
+impl<C: TestCase> Scan for TestCases<C> {
+    fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+        let count = scanner.next_line()?.next_ranged(C::COUNT)?;
+        let cases = (0..count)
+            .map(|_| C::Input::scan(scanner))
+            .collect::<scanner::Result<_>>()?;
+        Ok(TestCases(cases))
+    }
+}
+
+impl<C: TestCase> Problem for TestCases<C> {
+    type Input = Self;
+    type Output = Lines<C::Output>;
+
+    fn solve(input: Self) -> Result<Lines<C::Output>> {
+        let answers = input.0.into_iter().map(C::solve).collect::<Result<_>>()?;
+        Ok(Lines(answers))
+    }
+}
+
+/// Solves the problem for the standard input and prints the answer to the standard output.
+pub fn run<P: Problem>() -> Result<()> {
+    let scanner = StdinScanner::new(io::stdin());
+    run_with::<P>(scanner, io::stdout().lock())
+}
+
+/// Scans the whole input, checking that nothing is left unscanned according to the scanner's
+/// [`Validation`](scanner::Validation), solves the problem and writes the answer followed by a
+/// line break through a buffered [`Output`].
+pub fn run_with<P: Problem>(mut scanner: impl Scanner, writer: impl Write) -> Result<()> {
+    let input = P::Input::scan(&mut scanner)?;
+    scanner.finish()?;
+    let answer = P::solve(input)?;
+    let mut output = Output::new(writer);
+    output.line(answer)?;
+    output.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use scanner::StringScanner;
+
+    use super::*;
+
+    struct Difference {
+        minuend: u8,
+        subtrahend: u8,
+    }
+
+    impl Scan for Difference {
+        fn scan(scanner: &mut impl Scanner) -> scanner::Result<Self> {
+            let mut line = scanner.next_line()?;
+            Ok(Difference {
+                minuend: line.next_ranged(..)?,
+                subtrahend: line.next_ranged(..)?,
+            })
+        }
+    }
+
+    impl Problem for Difference {
+        type Input = Self;
+        type Output = u8;
+
+        fn solve(input: Self) -> Result<u8> {
+            let difference = input.minuend.checked_sub(input.subtrahend);
+            Ok(difference.ok_or("Negative difference")?)
+        }
+    }
+
+    impl TestCase for Difference {
+        type Input = Self;
+        type Output = u8;
+        const COUNT: RangeInclusive<u32> = 1..=3;
+
+        fn solve(input: Self) -> Result<u8> {
+            <Self as Problem>::solve(input)
+        }
+    }
+
+    fn run_on<P: Problem>(input: &str) -> Result<String> {
+        let mut output = Vec::new();
+        run_with::<P>(StringScanner::from(input).validating(), &mut output)?;
+        Ok(String::from_utf8(output)?)
+    }
+
+    #[test]
+    fn test_run_with() -> Result<()> {
+        assert_eq!("3\n", run_on::<Difference>("5 2\n")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_test_cases() -> Result<()> {
+        assert_eq!("3\n0\n", run_on::<TestCases<Difference>>("2\n5 2\n1 1\n")?);
+        assert_eq!(
+            "line 3: missing line",
+            run_on::<TestCases<Difference>>("2\n5 2\n")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "line 3, token 1: unexpected trailing `1`",
+            run_on::<TestCases<Difference>>("1\n5 2\n1 1\n")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "line 1, token 1: `4` is out of range",
+            run_on::<TestCases<Difference>>("4\n")
+                .unwrap_err()
+                .to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            "line 1, token 2: missing token",
+            run_on::<Difference>("5\n").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 2, token 1: unexpected trailing `1`",
+            run_on::<Difference>("5 2\n1\n").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "Negative difference",
+            run_on::<Difference>("2 5\n").unwrap_err().to_string()
+        );
+    }
+}
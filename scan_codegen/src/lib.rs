@@ -0,0 +1,202 @@
+//! Code generation behind `#[derive(Scan)]`, shared by the `scan_derive` proc-macro and the
+//! submission bundler, which expands the derive in place.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Expr, Fields, Ident, Result};
+
+struct Field {
+    name: Ident,
+    ty: syn::Type,
+    range: Option<Expr>,
+    len: Option<Expr>,
+    rest: bool,
+    same_line: bool,
+    nested: bool,
+}
+
+/// Generates the `scanner::Scan` implementation for a struct deriving `Scan`.
+///
+/// The output refers to the scanner crate as `scanner::` and its own variables are prefixed with
+/// `__`, so it also stays valid when printed as source and pasted next to the struct.
+pub fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new(input.span(), "Scan requires named fields")),
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "Scan can only be derived for structs",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "Scan does not support generics",
+        ));
+    }
+
+    let fields = fields.iter().map(parse_field).collect::<Result<Vec<_>>>()?;
+    let lines = group_lines(&fields)?;
+    let scanner = Ident::new("__scanner", Span::mixed_site());
+    let lines = lines.iter().map(|line| expand_line(line, &scanner));
+    let names = fields.iter().map(|field| &field.name);
+    let lengths = length_fields(&fields);
+
+    let name = &input.ident;
+    let value = Ident::new("__value", Span::mixed_site());
+    Ok(quote! {
+        impl scanner::Scan for #name {
+            fn scan(#scanner: &mut impl scanner::Scanner) -> scanner::Result<Self> {
+                #(#lines)*
+                let #value = Self { #(#names),* };
+                let _ = (#(&#value.#lengths,)*);
+                Ok(#value)
+            }
+        }
+    })
+}
+
+fn parse_field(field: &syn::Field) -> Result<Field> {
+    let mut parsed = Field {
+        name: field.ident.clone().expect("fields are named"),
+        ty: field.ty.clone(),
+        range: None,
+        len: None,
+        rest: false,
+        same_line: false,
+        nested: false,
+    };
+    for attribute in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("scan"))
+    {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("range") {
+                parsed.range = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("len") {
+                parsed.len = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("rest") {
+                parsed.rest = true;
+            } else if meta.path.is_ident("same_line") {
+                parsed.same_line = true;
+            } else if meta.path.is_ident("nested") {
+                parsed.nested = true;
+            } else {
+                return Err(meta.error("unknown scan attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let span = field.span();
+    if parsed.rest && parsed.len.is_some() {
+        return Err(Error::new(span, "`rest` and `len` cannot be combined"));
+    }
+    if parsed.nested && (parsed.same_line || parsed.rest || parsed.range.is_some()) {
+        return Err(Error::new(
+            span,
+            "`nested` fields take their own lines and check their own ranges",
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Returns fields used as the `len` of another field, to mark them as read.
+fn length_fields(fields: &[Field]) -> Vec<&Ident> {
+    let mut lengths = Vec::new();
+    for field in fields {
+        if let Some(Expr::Path(path)) = &field.len {
+            let length = fields
+                .iter()
+                .map(|field| &field.name)
+                .find(|name| path.path.is_ident(*name));
+            if let Some(length) = length {
+                if !lengths.contains(&length) {
+                    lengths.push(length);
+                }
+            }
+        }
+    }
+    lengths
+}
+
+/// Splits fields into groups read from a single line, nested fields form groups of their own.
+fn group_lines(fields: &[Field]) -> Result<Vec<Vec<&Field>>> {
+    let mut lines: Vec<Vec<&Field>> = Vec::new();
+    for field in fields {
+        match lines.last_mut() {
+            Some(line) if field.same_line => {
+                if line.iter().any(|field| field.nested || field.rest) {
+                    return Err(Error::new(
+                        field.name.span(),
+                        "`same_line` cannot follow a `nested` or `rest` field",
+                    ));
+                }
+                line.push(field);
+            }
+            None if field.same_line => {
+                return Err(Error::new(
+                    field.name.span(),
+                    "the first field cannot be on the same line",
+                ));
+            }
+            _ => lines.push(vec![field]),
+        }
+    }
+    Ok(lines)
+}
+
+fn expand_line(fields: &[&Field], scanner: &Ident) -> TokenStream2 {
+    let names = fields.iter().map(|field| &field.name).collect::<Vec<_>>();
+    if let [field] = fields {
+        if field.nested {
+            return expand_nested(field, scanner);
+        }
+    }
+
+    let line = Ident::new("__line", Span::mixed_site());
+    let reads = fields.iter().map(|field| {
+        let name = &field.name;
+        let ty = &field.ty;
+        let range = field
+            .range
+            .as_ref()
+            .map_or_else(|| quote!(..), |range| quote!(#range));
+        let read = if let Some(len) = &field.len {
+            quote!(scanner::LineScanner::next_vec(&mut #line, (#len) as usize, #range)?)
+        } else if field.rest {
+            quote!(scanner::LineScanner::rest(&mut #line, #range)?)
+        } else {
+            quote!(scanner::LineScanner::next_ranged(&mut #line, #range)?)
+        };
+        quote_spanned!(name.span()=> let #name: #ty = #read;)
+    });
+    quote! {
+        let (#(#names,)*) = {
+            let mut #line = scanner::Scanner::next_line(#scanner)?;
+            #(#reads)*
+            (#(#names,)*)
+        };
+    }
+}
+
+fn expand_nested(field: &Field, scanner: &Ident) -> TokenStream2 {
+    let name = &field.name;
+    let ty = &field.ty;
+    match &field.len {
+        Some(len) => quote! {
+            let #name: #ty = (0..(#len) as usize)
+                .map(|_| scanner::Scan::scan(&mut *#scanner))
+                .collect::<scanner::Result<_>>()?;
+        },
+        None => quote! {
+            let #name: #ty = scanner::Scan::scan(&mut *#scanner)?;
+        },
+    }
+}
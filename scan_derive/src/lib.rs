@@ -0,0 +1,27 @@
+//! `#[derive(Scan)]` for reading a problem's input struct from a `scanner::Scanner`.
+//!
+//! Fields are read in declaration order, each one starting a new input line unless it is marked
+//! with `same_line`. Field attributes:
+//!
+//! * `#[scan(range = 1..=100)]` checks the value, or every element of a vector, like
+//!   `LineScanner::next_ranged`;
+//! * `#[scan(len = count)]` reads a vector of `count` elements, where `count` is any expression,
+//!   usually an earlier field;
+//! * `#[scan(rest)]` reads a vector of all items left on the line;
+//! * `#[scan(same_line)]` continues the line of the previous field;
+//! * `#[scan(nested)]` reads a field whose type implements `Scan` itself, or with `len` a vector
+//!   of such values, each taking its own lines.
+//!
+//! Ranges and lengths may refer to fields declared before them. Fields that are only used as the
+//! `len` of a later field are not reported as unused.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, Error};
+
+#[proc_macro_derive(Scan, attributes(scan))]
+pub fn derive_scan(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    scan_codegen::expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
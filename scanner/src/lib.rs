@@ -1,124 +1,754 @@
-use std::io::{Stdin, StdinLock};
+//! Line oriented input scanning for problem solutions.
+//!
+//! A [`Scanner`] hands out one [`LineScanner`] per input line, which in turn parses whitespace
+//! separated items while checking them against the constraint ranges from the problem statement.
+//!
+//! ```
+//! use scanner::{LineScanner, Scanner, StringScanner};
+//!
+//! let mut scanner = StringScanner::from("2 3\n4");
+//! let mut line = scanner.next_line().unwrap();
+//! let (x, y): (u8, u8) = (line.next_ranged(..).unwrap(), line.next_ranged(..).unwrap());
+//! drop(line);
+//! let z: u8 = scanner.next_line().unwrap().next_ranged(1..=10).unwrap();
+//! assert_eq!((2, 3, 4), (x, y, z));
+//! ```
+//!
+//! Malformed input is reported as a [`ScanError`] pointing at the line and token it was found at.
+//!
+//! Range violations, tokens left on a line and input left unscanned are debug assertions by
+//! default. [`Validation::Strict`] turns them into returned errors in every build, so the same
+//! code can validate generated test inputs.
+//!
+//! Judges often wrap long sequences across lines arbitrarily, for such inputs
+//! [`Scanner::tokens`] switches to a [`TokenScanner`] that ignores line boundaries altogether.
+//!
+//! Input is read as raw bytes, the standard input in one go, and lines and tokens are slices of
+//! that buffer, so scanning does not allocate. Integers are parsed by hand, other types
+//! implementing [`Parse`] fall back to [`FromStr`](str::FromStr).
+//!
+//! With the `derive` feature, `#[derive(Scan)]` generates a [`Scan`] implementation reading a
+//! problem's input struct field by field, see the `scan_derive` crate for its attributes.
+//!
+//! The crate is a single file that depends only on `std` and never refers to itself through
+//! `crate::` paths. Judges that accept only one source file can therefore be given the contents
+//! of this file wrapped in `mod scanner { ... }`, after which `use scanner::...` imports in the
+//! solution keep working unchanged. The `bundle` tool does exactly that.
+
+use std::any;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read, Stdin};
 use std::ops::RangeBounds;
-use std::str::{FromStr, SplitWhitespace};
-use std::{io, str};
+use std::{mem, str, thread};
+
+#[cfg(feature = "derive")]
+pub use scan_derive::Scan;
+
+pub type Result<T, E = ScanError> = std::result::Result<T, E>;
+
+/// Imports the scanner traits, so that their methods can be called.
+pub mod prelude {
+    pub use super::{LineScanner, Parse, Scan, Scanner, TupleRanges};
+}
 
-type Result<T, E = Error> = std::result::Result<T, E>;
-type Error = Box<dyn std::error::Error>;
+/// Parses items of a single line.
+pub trait LineScanner {
+    /// Parses the next item of the line and checks it with `accept`, an item it rejects is out of
+    /// range and reported according to the scanner's [`Validation`].
+    ///
+    /// ```
+    /// # use scanner::{LineScanner, Scanner, StringScanner};
+    /// let mut scanner = StringScanner::from("abc abcd").validating();
+    /// let mut line = scanner.next_line().unwrap();
+    /// let word = line.next_checked::<String>(|word| word.len() == 3).unwrap();
+    /// assert_eq!("abc", word);
+    /// assert!(line.next_checked::<String>(|word| word.len() == 3).is_err());
+    /// ```
+    fn next_checked<U>(&mut self, accept: impl FnOnce(&U) -> bool) -> Result<U>
+    where
+        U: Parse;
 
-trait LineScanner {
+    /// Parses the next item of the line and checks that it lies in the given range.
     fn next_ranged<U>(&mut self, range: impl RangeBounds<U>) -> Result<U>
     where
-        U: PartialOrd + FromStr,
-        U::Err: std::error::Error + 'static;
+        U: PartialOrd + Parse,
+    {
+        self.next_checked(|value| range.contains(value))
+    }
+
+    /// Returns true if there are no more items to parse.
+    fn is_empty(&self) -> bool;
+
+    /// Parses the next items into a tuple, checking each against its range from `ranges`.
+    ///
+    /// ```
+    /// # use scanner::{LineScanner, Scanner, StringScanner};
+    /// let mut scanner = StringScanner::from("1 -2 c");
+    /// let mut line = scanner.next_line().unwrap();
+    /// let tuple = line.next_tuple::<(u8, i8, char)>((1..=3, -2..0, ..)).unwrap();
+    /// assert_eq!((1, -2, 'c'), tuple);
+    /// ```
+    fn next_tuple<T>(&mut self, ranges: impl TupleRanges<T>) -> Result<T>
+    where
+        Self: Sized,
+    {
+        ranges.next_tuple(self)
+    }
+
+    /// Parses the next `N` items into an array, checking each against `range`.
+    fn next_array<U, const N: usize>(&mut self, range: impl RangeBounds<U>) -> Result<[U; N]>
+    where
+        U: PartialOrd + Parse,
+    {
+        let items = self.next_vec(N, range)?;
+        match items.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!("next_vec returns exactly N items"),
+        }
+    }
+
+    /// Parses the next `count` items, checking each against `range`.
+    fn next_vec<U>(&mut self, count: usize, range: impl RangeBounds<U>) -> Result<Vec<U>>
+    where
+        U: PartialOrd + Parse,
+    {
+        let range = (range.start_bound(), range.end_bound());
+        (0..count).map(|_| self.next_ranged(range)).collect()
+    }
+
+    /// Parses all remaining items, checking each against `range`.
+    fn rest<U>(&mut self, range: impl RangeBounds<U>) -> Result<Vec<U>>
+    where
+        U: PartialOrd + Parse,
+    {
+        let range = (range.start_bound(), range.end_bound());
+        let mut items = Vec::new();
+        while !self.is_empty() {
+            items.push(self.next_ranged(range)?);
+        }
+        Ok(items)
+    }
+}
+
+/// Tuple of ranges, one for each item of a tuple parsed by [`LineScanner::next_tuple`].
+pub trait TupleRanges<T> {
+    fn next_tuple(self, line: &mut impl LineScanner) -> Result<T>;
 }
 
-trait Scanner {
+macro_rules! impl_tuple_ranges {
+    ($(($($item:ident: $range:ident),+)),+) => {
+        $(
+            impl<$($item, $range),+> TupleRanges<($($item,)+)> for ($($range,)+)
+            where
+                $($item: PartialOrd + Parse, $range: RangeBounds<$item>),+
+            {
+                #[allow(non_snake_case)]
+                fn next_tuple(self, line: &mut impl LineScanner) -> Result<($($item,)+)> {
+                    let ($($range,)+) = self;
+                    Ok(($(line.next_ranged::<$item>($range)?,)+))
+                }
+            }
+        )+
+    };
+}
+
+impl_tuple_ranges!(
+    (A: RA),
+    (A: RA, B: RB),
+    (A: RA, B: RB, C: RC),
+    (A: RA, B: RB, C: RC, D: RD),
+    (A: RA, B: RB, C: RC, D: RD, E: RE),
+    (A: RA, B: RB, C: RC, D: RD, E: RE, F: RF)
+);
+
+/// Splits the input into lines.
+pub trait Scanner {
     type LineScanner<'a>: LineScanner
     where
         Self: 'a;
 
+    /// Returns a scanner of the next input line.
     fn next_line(&mut self) -> Result<Self::LineScanner<'_>>;
+
+    /// Returns a scanner of all remaining tokens, regardless of how they are split into lines.
+    fn tokens(&mut self) -> Result<TokenScanner<'_>>;
+
+    /// Checks that no input is left unscanned, like the other violations an error only in
+    /// [`Validation::Strict`] mode.
+    fn finish(self) -> Result<()>
+    where
+        Self: Sized;
+}
+
+/// Input that can be read from a scanner as a whole, usually derived with `#[derive(Scan)]`.
+pub trait Scan: Sized {
+    fn scan(scanner: &mut impl Scanner) -> Result<Self>;
+}
+
+/// Error in the scanned input. Lines and tokens within a line are numbered from 1.
+#[derive(Debug)]
+pub enum ScanError {
+    Io(io::Error),
+    MissingLine {
+        line: usize,
+    },
+    MissingToken {
+        line: usize,
+        token: usize,
+    },
+    Parse {
+        line: usize,
+        token: usize,
+        text: String,
+        expected: &'static str,
+    },
+    OutOfRange {
+        line: usize,
+        token: usize,
+        text: String,
+    },
+    TrailingTokens {
+        line: usize,
+        token: usize,
+        text: String,
+    },
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::Io(error) => write!(f, "Cannot read input: {error}"),
+            ScanError::MissingLine { line } => write!(f, "line {line}: missing line"),
+            ScanError::MissingToken { line, token } => {
+                write!(f, "line {line}, token {token}: missing token")
+            }
+            ScanError::Parse {
+                line,
+                token,
+                text,
+                expected,
+            } => write!(
+                f,
+                "line {line}, token {token}: cannot parse `{text}` as {expected}"
+            ),
+            ScanError::OutOfRange { line, token, text } => {
+                write!(f, "line {line}, token {token}: `{text}` is out of range")
+            }
+            ScanError::TrailingTokens { line, token, text } => {
+                write!(
+                    f,
+                    "line {line}, token {token}: unexpected trailing `{text}`"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScanError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ScanError {
+    fn from(value: io::Error) -> Self {
+        ScanError::Io(value)
+    }
+}
+
+/// Parses a single whitespace-free token, returning `None` if it is malformed.
+pub trait Parse: Sized {
+    fn parse(token: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_parse_unsigned {
+    ($($ty:ty),+) => {
+        $(
+            impl Parse for $ty {
+                fn parse(token: &[u8]) -> Option<Self> {
+                    let digits = token.strip_prefix(b"+").unwrap_or(token);
+                    if digits.is_empty() {
+                        return None;
+                    }
+                    let mut value: $ty = 0;
+                    for digit in digits {
+                        let digit = digit.wrapping_sub(b'0');
+                        if digit > 9 {
+                            return None;
+                        }
+                        value = value.checked_mul(10)?.checked_add(digit as $ty)?;
+                    }
+                    Some(value)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_parse_signed {
+    ($($ty:ty),+) => {
+        $(
+            impl Parse for $ty {
+                fn parse(token: &[u8]) -> Option<Self> {
+                    let (negative, digits) = match token {
+                        [b'-', digits @ ..] => (true, digits),
+                        [b'+', digits @ ..] => (false, digits),
+                        digits => (false, digits),
+                    };
+                    if digits.is_empty() {
+                        return None;
+                    }
+                    // Accumulating towards the sign of the result lets `MIN` be parsed too.
+                    let mut value: $ty = 0;
+                    for digit in digits {
+                        let digit = digit.wrapping_sub(b'0');
+                        if digit > 9 {
+                            return None;
+                        }
+                        let digit = digit as $ty;
+                        value = if negative {
+                            value.checked_mul(10)?.checked_sub(digit)?
+                        } else {
+                            value.checked_mul(10)?.checked_add(digit)?
+                        };
+                    }
+                    Some(value)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_parse_from_str {
+    ($($ty:ty),+) => {
+        $(
+            impl Parse for $ty {
+                fn parse(token: &[u8]) -> Option<Self> {
+                    str::from_utf8(token).ok()?.parse().ok()
+                }
+            }
+        )+
+    };
+}
+
+impl_parse_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_parse_signed!(i8, i16, i32, i64, i128, isize);
+impl_parse_from_str!(f32, f64, bool, char, String);
+
+/// How the scanners treat input that violates the problem constraints.
+///
+/// Violations are values out of the requested range, tokens left unparsed on a line and input
+/// left unscanned at the end.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Validation {
+    /// Violations panic in debug builds and are ignored in release builds.
+    #[default]
+    Debug,
+    /// Violations are returned as errors in every build, which is useful for validating
+    /// generated test inputs. Unscanned input is reported by the next call to
+    /// [`Scanner::next_line`], [`Scanner::tokens`] or [`Scanner::finish`].
+    Strict,
 }
 
-struct StringScanner<'a> {
-    input: str::Lines<'a>,
+/// Scans an in-memory string, mostly useful in tests.
+pub struct StringScanner<'a> {
+    input: &'a [u8],
+    line: usize,
+    validation: Validation,
+    trailing: Option<ScanError>,
 }
 
 impl<'a> StringScanner<'a> {
-    fn new(input: str::Lines<'a>) -> Self {
-        Self { input }
+    pub fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            line: 0,
+            validation: Validation::default(),
+            trailing: None,
+        }
+    }
+
+    /// Switches the scanner to [`Validation::Strict`].
+    pub fn validating(mut self) -> Self {
+        self.validation = Validation::Strict;
+        self
     }
 }
 
 impl<'b> Scanner for StringScanner<'b> {
-    type LineScanner<'a> = LineSplitScanner<'a>
+    type LineScanner<'a>
+        = LineSplitScanner<'a>
     where
         Self: 'a;
 
     fn next_line(&mut self) -> Result<Self::LineScanner<'_>> {
-        let next_line = self.input.next().ok_or("Missing next line")?;
-        let line = next_line.split_whitespace();
-        Ok(LineSplitScanner { line })
+        take_trailing(&mut self.trailing)?;
+        self.line += 1;
+        let line = split_line(&mut self.input).ok_or(ScanError::MissingLine { line: self.line })?;
+        let line = Tokens::new(line, self.line, self.validation);
+        Ok(LineSplitScanner::with_trailing(line, &mut self.trailing))
+    }
+
+    fn tokens(&mut self) -> Result<TokenScanner<'_>> {
+        take_trailing(&mut self.trailing)?;
+        let rest = mem::take(&mut self.input);
+        let tokens = Tokens::new(rest, self.line + 1, self.validation);
+        Ok(TokenScanner::with_trailing(tokens, &mut self.trailing))
+    }
+
+    fn finish(mut self) -> Result<()> {
+        take_trailing(&mut self.trailing)?;
+        let rest = mem::take(&mut self.input);
+        check_rest(Tokens::new(rest, self.line + 1, self.validation))
     }
 }
 
 impl<'a> From<&'a str> for StringScanner<'a> {
     fn from(value: &'a str) -> Self {
-        let lines = value.lines();
-        Self::new(lines)
+        Self::new(value.as_bytes())
     }
 }
 
 impl<'a> Drop for StringScanner<'a> {
     fn drop(&mut self) {
-        debug_assert!(self.input.next().is_none());
+        if self.validation == Validation::Debug {
+            assert_no_trailing_tokens(Tokens::new(self.input, self.line + 1, self.validation));
+        }
     }
 }
 
-struct StdinScanner {
-    input: io::Lines<StdinLock<'static>>,
-    current_line: Option<String>,
+/// Scans the standard input, which is read whole on the first request for a line.
+pub struct StdinScanner {
+    stdin: Option<Stdin>,
+    input: Vec<u8>,
+    position: usize,
+    line: usize,
+    validation: Validation,
+    trailing: Option<ScanError>,
 }
 
 impl StdinScanner {
-    fn new(input: io::Lines<StdinLock<'static>>) -> Self {
+    pub fn new(stdin: Stdin) -> Self {
         Self {
-            input,
-            current_line: None,
+            stdin: Some(stdin),
+            input: Vec::new(),
+            position: 0,
+            line: 0,
+            validation: Validation::default(),
+            trailing: None,
         }
     }
+
+    /// Switches the scanner to [`Validation::Strict`].
+    pub fn validating(mut self) -> Self {
+        self.validation = Validation::Strict;
+        self
+    }
+
+    fn read_input(&mut self) -> Result<()> {
+        if let Some(stdin) = self.stdin.take() {
+            stdin.lock().read_to_end(&mut self.input)?;
+        }
+        Ok(())
+    }
 }
 
 impl Scanner for StdinScanner {
-    type LineScanner<'a> = LineSplitScanner<'a>
+    type LineScanner<'a>
+        = LineSplitScanner<'a>
     where
         Self: 'a;
 
     fn next_line(&mut self) -> Result<Self::LineScanner<'_>> {
-        let next_line = self.input.next().ok_or("Missing next line")??;
-        let line = self.current_line.insert(next_line);
-        let line = line.split_whitespace();
-        Ok(LineSplitScanner { line })
+        take_trailing(&mut self.trailing)?;
+        self.read_input()?;
+        self.line += 1;
+        let mut input = &self.input[self.position..];
+        let line = split_line(&mut input).ok_or(ScanError::MissingLine { line: self.line })?;
+        self.position = self.input.len() - input.len();
+        let line = Tokens::new(line, self.line, self.validation);
+        Ok(LineSplitScanner::with_trailing(line, &mut self.trailing))
+    }
+
+    fn tokens(&mut self) -> Result<TokenScanner<'_>> {
+        take_trailing(&mut self.trailing)?;
+        self.read_input()?;
+        let rest = &self.input[self.position..];
+        self.position = self.input.len();
+        let tokens = Tokens::new(rest, self.line + 1, self.validation);
+        Ok(TokenScanner::with_trailing(tokens, &mut self.trailing))
+    }
+
+    fn finish(mut self) -> Result<()> {
+        take_trailing(&mut self.trailing)?;
+        self.read_input()?;
+        let rest = &self.input[self.position..];
+        let result = check_rest(Tokens::new(rest, self.line + 1, self.validation));
+        self.position = self.input.len();
+        result
     }
 }
 
 impl From<Stdin> for StdinScanner {
     fn from(value: Stdin) -> Self {
-        let lines = value.lines();
-        Self::new(lines)
+        Self::new(value)
     }
 }
 
 impl Drop for StdinScanner {
     fn drop(&mut self) {
-        debug_assert!(self.input.next().is_none());
+        if self.validation == Validation::Debug {
+            let rest = &self.input[self.position..];
+            assert_no_trailing_tokens(Tokens::new(rest, self.line + 1, self.validation));
+        }
     }
 }
 
-struct LineSplitScanner<'a> {
-    line: SplitWhitespace<'a>,
+/// Scans whitespace separated items of a single line.
+pub struct LineSplitScanner<'a> {
+    line: Tokens<'a>,
+    trailing: Option<&'a mut Option<ScanError>>,
+}
+
+impl<'a> LineSplitScanner<'a> {
+    pub fn new(line: &'a [u8], line_number: usize) -> Self {
+        Self {
+            line: Tokens::new(line, line_number, Validation::Debug),
+            trailing: None,
+        }
+    }
+
+    fn with_trailing(line: Tokens<'a>, trailing: &'a mut Option<ScanError>) -> Self {
+        Self {
+            line,
+            trailing: Some(trailing),
+        }
+    }
 }
 
 impl<'a> LineScanner for LineSplitScanner<'a> {
-    fn next_ranged<U>(&mut self, range: impl RangeBounds<U>) -> Result<U>
+    fn next_checked<U>(&mut self, accept: impl FnOnce(&U) -> bool) -> Result<U>
     where
-        U: PartialOrd + FromStr,
-        U::Err: std::error::Error + 'static,
+        U: Parse,
     {
-        let item = self.line.next().ok_or("Missing next item")?;
-        let parsed = item.parse::<U>()?;
-        debug_assert!(range.contains(&parsed));
-        Ok(parsed)
+        self.line.next_checked(accept)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.line.is_empty()
     }
 }
 
 impl<'a> Drop for LineSplitScanner<'a> {
     fn drop(&mut self) {
-        debug_assert!(self.line.next().is_none());
+        match (self.line.validation, &mut self.trailing) {
+            (Validation::Strict, Some(trailing)) => **trailing = self.line.clone().trailing(),
+            _ => assert_no_trailing_tokens(self.line.clone()),
+        }
+    }
+}
+
+/// Scans whitespace separated tokens, treating line breaks as any other whitespace.
+///
+/// Unlike [`LineSplitScanner`] it does not require all tokens to be consumed, unless it comes
+/// from a scanner in [`Validation::Strict`] mode.
+pub struct TokenScanner<'a> {
+    tokens: Tokens<'a>,
+    trailing: Option<&'a mut Option<ScanError>>,
+}
+
+impl<'a> TokenScanner<'a> {
+    pub fn new(tokens: &'a [u8], line_number: usize) -> Self {
+        Self {
+            tokens: Tokens::new(tokens, line_number, Validation::Debug),
+            trailing: None,
+        }
+    }
+
+    fn with_trailing(tokens: Tokens<'a>, trailing: &'a mut Option<ScanError>) -> Self {
+        Self {
+            tokens,
+            trailing: Some(trailing),
+        }
+    }
+
+    /// Parses the next token without any range constraint.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<U>(&mut self) -> Result<U>
+    where
+        U: PartialOrd + Parse,
+    {
+        self.next_ranged(..)
+    }
+
+    /// Parses the next `count` tokens.
+    pub fn next_n<U>(&mut self, count: usize) -> Result<Vec<U>>
+    where
+        U: PartialOrd + Parse,
+    {
+        (0..count).map(|_| self.next()).collect()
+    }
+}
+
+impl<'a> LineScanner for TokenScanner<'a> {
+    fn next_checked<U>(&mut self, accept: impl FnOnce(&U) -> bool) -> Result<U>
+    where
+        U: Parse,
+    {
+        self.tokens.next_checked(accept)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+impl<'a> From<&'a str> for TokenScanner<'a> {
+    fn from(value: &'a str) -> Self {
+        Self::new(value.as_bytes(), 1)
+    }
+}
+
+impl<'a> Drop for TokenScanner<'a> {
+    fn drop(&mut self) {
+        if let (Validation::Strict, Some(trailing)) = (self.tokens.validation, &mut self.trailing) {
+            **trailing = self.tokens.clone().trailing();
+        }
     }
 }
 
+/// Whitespace separated tokens together with the position of the last one returned.
+#[derive(Clone)]
+struct Tokens<'a> {
+    input: &'a [u8],
+    line: usize,
+    token: usize,
+    validation: Validation,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(input: &'a [u8], line: usize, validation: Validation) -> Self {
+        Self {
+            input,
+            line,
+            token: 0,
+            validation,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.input.iter().all(|byte| byte.is_ascii_whitespace())
+    }
+
+    /// Cuts the next token off the input, skipping the whitespace and line breaks before it.
+    fn next_token(&mut self) -> Option<&'a [u8]> {
+        let start = self
+            .input
+            .iter()
+            .position(|byte| !byte.is_ascii_whitespace())?;
+        let line_breaks = self.input[..start]
+            .iter()
+            .filter(|byte| **byte == b'\n')
+            .count();
+        if line_breaks > 0 {
+            self.line += line_breaks;
+            self.token = 0;
+        }
+        self.token += 1;
+
+        let rest = &self.input[start..];
+        let end = rest
+            .iter()
+            .position(|byte| byte.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        self.input = &rest[end..];
+        Some(&rest[..end])
+    }
+
+    fn next_checked<U>(&mut self, accept: impl FnOnce(&U) -> bool) -> Result<U>
+    where
+        U: Parse,
+    {
+        let Some(token) = self.next_token() else {
+            return Err(ScanError::MissingToken {
+                line: self.line,
+                token: self.token + 1,
+            });
+        };
+        let parsed = U::parse(token).ok_or_else(|| ScanError::Parse {
+            line: self.line,
+            token: self.token,
+            text: String::from_utf8_lossy(token).into_owned(),
+            expected: any::type_name::<U>(),
+        })?;
+
+        let strict = self.validation == Validation::Strict;
+        if (strict || cfg!(debug_assertions)) && !accept(&parsed) {
+            let error = ScanError::OutOfRange {
+                line: self.line,
+                token: self.token,
+                text: String::from_utf8_lossy(token).into_owned(),
+            };
+            if strict {
+                return Err(error);
+            }
+            panic!("{error}");
+        }
+        Ok(parsed)
+    }
+
+    /// Returns an error describing the next token, if there is any.
+    fn trailing(mut self) -> Option<ScanError> {
+        let token = self.next_token()?;
+        Some(ScanError::TrailingTokens {
+            line: self.line,
+            token: self.token,
+            text: String::from_utf8_lossy(token).into_owned(),
+        })
+    }
+}
+
+/// Returns the trailing tokens error recorded by a previously dropped line, if there is any.
+fn take_trailing(trailing: &mut Option<ScanError>) -> Result<()> {
+    trailing.take().map_or(Ok(()), Err)
+}
+
+/// Checks the input left at the end of scanning, which has to be empty.
+fn check_rest(rest: Tokens) -> Result<()> {
+    match rest.validation {
+        Validation::Strict => rest.trailing().map_or(Ok(()), Err),
+        Validation::Debug => {
+            assert_no_trailing_tokens(rest);
+            Ok(())
+        }
+    }
+}
+
+/// Panics in debug builds if there are tokens left, unless the thread is already panicking.
+fn assert_no_trailing_tokens(tokens: Tokens) {
+    if cfg!(debug_assertions) && !thread::panicking() {
+        if let Some(error) = tokens.trailing() {
+            panic!("{error}");
+        }
+    }
+}
+
+/// Cuts the first line off the input, without its line terminator.
+fn split_line<'a>(input: &mut &'a [u8]) -> Option<&'a [u8]> {
+    if input.is_empty() {
+        return None;
+    }
+    let (line, rest) = match input.iter().position(|byte| *byte == b'\n') {
+        Some(end) => (&input[..end], &input[end + 1..]),
+        None => (*input, &[][..]),
+    };
+    *input = rest;
+    Some(line.strip_suffix(b"\r").unwrap_or(line))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,4 +770,198 @@ mod tests {
         assert_eq!(((1, 2, 3), 1), ((x, y, z), a));
         Ok(())
     }
+
+    #[test]
+    fn test_tokens() -> Result<()> {
+        let mut scanner = StringScanner::from(
+            r#"5 2
+            1 2
+            3
+            4 5
+            -1 x"#,
+        );
+        let (count, other) = {
+            let mut line = scanner.next_line()?;
+            let count = line.next_ranged(1..=5)?;
+            let other = line.next_ranged(0..=2)?;
+            (count, other)
+        };
+        let mut tokens = scanner.tokens()?;
+        let items = tokens.next_n::<u8>(count)?;
+        let negative = tokens.next_ranged::<i8>(-1..=0)?;
+        let letter = tokens.next::<char>()?;
+        assert!(tokens.next::<u8>().is_err());
+        assert_eq!((2, vec![1, 2, 3, 4, 5]), (other, items));
+        assert_eq!((-1, 'x'), (negative, letter));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_integers() {
+        assert_eq!(Some(255), u8::parse(b"255"));
+        assert_eq!(Some(7), u32::parse(b"+007"));
+        assert_eq!(Some(10u64.pow(14)), u64::parse(b"100000000000000"));
+        assert_eq!(
+            Some(u128::MAX),
+            u128::parse(u128::MAX.to_string().as_bytes())
+        );
+        assert_eq!(Some(i8::MIN), i8::parse(b"-128"));
+        assert_eq!(Some(i64::MAX), i64::parse(b"9223372036854775807"));
+        assert_eq!(Some(-5), i16::parse(b"-5"));
+        assert_eq!(None, u8::parse(b"256"));
+        assert_eq!(None, i8::parse(b"128"));
+        assert_eq!(None, u32::parse(b"-1"));
+        assert_eq!(None, i32::parse(b"-"));
+        assert_eq!(None, u32::parse(b"1x"));
+        assert_eq!(None, u32::parse(b""));
+    }
+
+    #[test]
+    fn test_parse_fallback() {
+        assert_eq!(Some(2.5), f64::parse(b"2.5"));
+        assert_eq!(Some("abc".to_owned()), String::parse(b"abc"));
+        assert_eq!(Some('z'), char::parse(b"z"));
+        assert_eq!(None, char::parse(b"zz"));
+    }
+
+    #[test]
+    fn test_line_endings() -> Result<()> {
+        let mut scanner = StringScanner::from("1 2\r\n\r\n 3\n");
+        let (x, y) = {
+            let mut line = scanner.next_line()?;
+            (line.next_ranged::<u8>(..)?, line.next_ranged::<u8>(..)?)
+        };
+        drop(scanner.next_line()?);
+        let z = scanner.next_line()?.next_ranged::<u8>(..)?;
+        assert!(scanner.next_line().is_err());
+        assert_eq!((1, 2, 3), (x, y, z));
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let mut scanner = StringScanner::from("1\n3 x\n");
+        scanner.next_line().unwrap().next_ranged::<u8>(..).unwrap();
+        let mut line = scanner.next_line().unwrap();
+        line.next_ranged::<u8>(..).unwrap();
+        let error = line.next_ranged::<u8>(..).unwrap_err();
+        assert_eq!("line 2, token 2: cannot parse `x` as u8", error.to_string());
+        let error = line.next_ranged::<u8>(..).unwrap_err();
+        assert_eq!("line 2, token 3: missing token", error.to_string());
+        drop(line);
+        let error = scanner.next_line().err().unwrap();
+        assert_eq!("line 3: missing line", error.to_string());
+
+        let mut tokens = TokenScanner::from("1\n\n 2 3\n-4");
+        tokens.next_n::<u8>(3).unwrap();
+        let error = tokens.next::<u8>().unwrap_err();
+        assert_eq!(
+            "line 4, token 1: cannot parse `-4` as u8",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1, token 2: `20` is out of range")]
+    #[cfg(debug_assertions)]
+    fn test_out_of_range() {
+        let mut scanner = StringScanner::from("1 20");
+        let mut line = scanner.next_line().unwrap();
+        let _ = line.next_ranged(0..10);
+        let _ = line.next_ranged(0..10);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1, token 2: unexpected trailing `2`")]
+    #[cfg(debug_assertions)]
+    fn test_trailing_tokens() {
+        let mut scanner = StringScanner::from("1 2");
+        let _ = scanner.next_line().unwrap().next_ranged::<u8>(..);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2, token 1: unexpected trailing `2`")]
+    #[cfg(debug_assertions)]
+    fn test_unscanned_input() {
+        let mut scanner = StringScanner::from("1\n2");
+        let _ = scanner.next_line().unwrap().next_ranged::<u8>(..);
+        let _ = scanner.finish();
+    }
+
+    #[test]
+    fn test_strict_validation() {
+        let mut scanner = StringScanner::from("1 20\n3 4\n5").validating();
+        let mut line = scanner.next_line().unwrap();
+        line.next_ranged::<u8>(..).unwrap();
+        let error = line.next_ranged::<u8>(0..10).unwrap_err();
+        assert_eq!("line 1, token 2: `20` is out of range", error.to_string());
+        drop(line);
+
+        scanner.next_line().unwrap().next_ranged::<u8>(..).unwrap();
+        let error = scanner.next_line().err().unwrap();
+        assert_eq!(
+            "line 2, token 2: unexpected trailing `4`",
+            error.to_string()
+        );
+
+        let error = scanner.finish().unwrap_err();
+        assert_eq!(
+            "line 3, token 1: unexpected trailing `5`",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_strict_tokens() {
+        let mut scanner = StringScanner::from("2\n1\n2 3\n").validating();
+        let count = scanner.next_line().unwrap().next_ranged(..).unwrap();
+        scanner.tokens().unwrap().next_n::<u8>(count).unwrap();
+        let error = scanner.finish().unwrap_err();
+        assert_eq!(
+            "line 3, token 2: unexpected trailing `3`",
+            error.to_string()
+        );
+
+        let mut scanner = StringScanner::from("1\n2\n\n").validating();
+        scanner.tokens().unwrap().next_n::<u8>(2).unwrap();
+        assert!(scanner.finish().is_ok());
+    }
+
+    #[test]
+    fn test_line_helpers() -> Result<()> {
+        let mut scanner = StringScanner::from(
+            r#"1 2 3
+            4 5 6 7
+            8 9
+            10 11 12"#,
+        );
+        let tuple = scanner
+            .next_line()?
+            .next_tuple::<(u8, i16, u32)>((0..10, .., 3..=3))?;
+        let array = scanner.next_line()?.next_array::<u8, 4>(4..=7)?;
+        let vector = scanner.next_line()?.next_vec::<u8>(2, 8..10)?;
+        let rest = {
+            let mut line = scanner.next_line()?;
+            let first = line.next_ranged::<u8>(..)?;
+            (first, line.rest::<u8>(11..)?)
+        };
+        assert_eq!((1, 2, 3), tuple);
+        assert_eq!([4, 5, 6, 7], array);
+        assert_eq!(vec![8, 9], vector);
+        assert_eq!((10, vec![11, 12]), rest);
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_helpers_errors() {
+        let mut scanner = StringScanner::from("1 2\n3 4 5").validating();
+        let error = scanner
+            .next_line()
+            .unwrap()
+            .next_tuple::<(u8, u8, u8)>((.., .., ..))
+            .unwrap_err();
+        assert_eq!("line 1, token 3: missing token", error.to_string());
+        let error = scanner.next_line().unwrap().rest::<u8>(..5).unwrap_err();
+        assert_eq!("line 2, token 3: `5` is out of range", error.to_string());
+    }
 }
@@ -0,0 +1,90 @@
+use scanner::{Result, Scan, Scanner, StringScanner};
+
+#[derive(Debug, PartialEq, scan_derive::Scan)]
+struct Siege {
+    #[scan(range = 1..=200_000)]
+    warrior_count: u32,
+    #[scan(same_line, range = 1..=200_000)]
+    battle_time: u32,
+    #[scan(len = warrior_count, range = 1..=10u32.pow(9))]
+    warriors_strengths: Vec<u32>,
+    #[scan(len = battle_time, range = 1..=10u64.pow(14))]
+    arrows: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq, scan_derive::Scan)]
+struct Groups {
+    #[scan(range = 1..=10)]
+    word_count: u8,
+    #[scan(same_line, range = 1..=word_count)]
+    group_count: u8,
+    #[scan(len = word_count)]
+    words: Vec<String>,
+    #[scan(nested, len = group_count)]
+    groups: Vec<Group>,
+    #[scan(nested)]
+    last: Group,
+}
+
+#[derive(Debug, PartialEq, scan_derive::Scan)]
+struct Group {
+    name: char,
+    #[scan(same_line, rest, range = 1..=10)]
+    members: Vec<u8>,
+}
+
+#[test]
+fn test_lines_and_lengths() -> Result<()> {
+    let mut scanner = StringScanner::from("5 5\n1 2 1 2 1\n3 10 1 1 1");
+    let siege = Siege::scan(&mut scanner)?;
+    scanner.finish()?;
+    let expected = Siege {
+        warrior_count: 5,
+        battle_time: 5,
+        warriors_strengths: vec![1, 2, 1, 2, 1],
+        arrows: vec![3, 10, 1, 1, 1],
+    };
+    assert_eq!(expected, siege);
+    Ok(())
+}
+
+#[test]
+fn test_nested_and_rest() -> Result<()> {
+    let mut scanner = StringScanner::from("3 2\ni am here\na 1 3\nb\nc 2");
+    let groups = Groups::scan(&mut scanner)?;
+    scanner.finish()?;
+    let group = |name, members: &[u8]| Group {
+        name,
+        members: members.to_vec(),
+    };
+    let expected = Groups {
+        word_count: 3,
+        group_count: 2,
+        words: vec!["i".to_owned(), "am".to_owned(), "here".to_owned()],
+        groups: vec![group('a', &[1, 3]), group('b', &[])],
+        last: group('c', &[2]),
+    };
+    assert_eq!(expected, groups);
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    let mut scanner = StringScanner::from("2 1\n1 2\nx");
+    let error = Siege::scan(&mut scanner).unwrap_err();
+    assert_eq!(
+        "line 3, token 1: cannot parse `x` as u64",
+        error.to_string()
+    );
+
+    let mut scanner = StringScanner::from("2 1\n1 2 3\n").validating();
+    let error = Siege::scan(&mut scanner).unwrap_err();
+    assert_eq!(
+        "line 2, token 3: unexpected trailing `3`",
+        error.to_string()
+    );
+
+    let mut scanner = StringScanner::from("2 1\n1 0\n1").validating();
+    let error = Siege::scan(&mut scanner).unwrap_err();
+    assert_eq!("line 2, token 2: `0` is out of range", error.to_string());
+}
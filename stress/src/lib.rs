@@ -0,0 +1,192 @@
+//! Stress tests comparing an optimized solver with a simple reference on random inputs.
+//!
+//! A problem implements [`Stress`] in its tests, naming an input generator, the reference solver,
+//! usually a brute force that is easy to trust, and the optimized solver used by the solution.
+//! [`check`] runs both on generated inputs of growing sizes, every iteration drawing from a
+//! [`Random`] generator of its own seed, so that it can be reproduced. On the first disagreement
+//! the failing input is shrunk, by repeatedly replacing it with the first of its
+//! [`Stress::shrink`] candidates on which the solvers still disagree, and reported along with both
+//! answers.
+//!
+//! ```
+//! use stress::{Random, Stress};
+//!
+//! struct Maximum;
+//!
+//! impl Stress for Maximum {
+//!     type Input = Vec<u64>;
+//!     type Output = u64;
+//!     const MAX_SIZE: usize = 20;
+//!
+//!     fn generate(random: &mut Random, size: usize) -> Vec<u64> {
+//!         (0..size).map(|_| random.range(0..=100)).collect()
+//!     }
+//!
+//!     fn reference(input: &Vec<u64>) -> u64 {
+//!         let mut sorted = input.clone();
+//!         sorted.sort();
+//!         sorted.last().copied().unwrap_or(0)
+//!     }
+//!
+//!     fn solve(input: &Vec<u64>) -> u64 {
+//!         input.iter().copied().max().unwrap_or(0)
+//!     }
+//!
+//!     fn shrink(input: &Vec<u64>) -> Vec<Vec<u64>> {
+//!         stress::removals(input)
+//!     }
+//! }
+//!
+//! stress::check::<Maximum>(1000, 42);
+//! ```
+
+use std::fmt::Debug;
+
+pub use generator::Random;
+
+/// A pair of solvers expected to agree on every input.
+pub trait Stress {
+    type Input: Debug;
+    type Output: PartialEq + Debug;
+    /// The largest size passed to [`Stress::generate`].
+    const MAX_SIZE: usize;
+
+    /// Generates a random valid input, whose size, such as the number of elements, grows with
+    /// `size`, which lies in `1..=MAX_SIZE`.
+    fn generate(random: &mut Random, size: usize) -> Self::Input;
+
+    fn reference(input: &Self::Input) -> Self::Output;
+
+    fn solve(input: &Self::Input) -> Self::Output;
+
+    /// Returns valid inputs smaller than the given one, such as the ones with an element removed,
+    /// tried in order while shrinking a failing input. There are none by default.
+    fn shrink(_input: &Self::Input) -> Vec<Self::Input> {
+        Vec::new()
+    }
+}
+
+/// Returns the copies of the items with one of them removed, the usual shrink candidates of a
+/// list of independent items.
+pub fn removals<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    (0..items.len())
+        .map(|index| [&items[..index], &items[index + 1..]].concat())
+        .collect()
+}
+
+/// Compares the solvers on `iterations` inputs, the `i`-th generated from the seed `seed + i`,
+/// with sizes cycling through `1..=MAX_SIZE`. Panics with the first failing input, shrunk.
+#[track_caller]
+pub fn check<S: Stress>(iterations: u64, seed: u64) {
+    for iteration in 0..iterations {
+        let seed = seed.wrapping_add(iteration);
+        let size = (iteration % S::MAX_SIZE as u64) as usize + 1;
+        let input = S::generate(&mut Random::new(seed), size);
+        if !agree::<S>(&input) {
+            let (input, steps) = shrink::<S>(input);
+            let expected = S::reference(&input);
+            let actual = S::solve(&input);
+            panic!(
+                "solvers disagree on the input generated with size {size} from seed {seed}, \
+                 shrunk in {steps} steps\ninput: {input:?}\nreference: {expected:?}\n\
+                 solver: {actual:?}"
+            );
+        }
+    }
+}
+
+/// Replaces the failing input with the first of its candidates on which the solvers still
+/// disagree until there is none, returning the last input and the number of replacements.
+fn shrink<S: Stress>(mut input: S::Input) -> (S::Input, usize) {
+    let mut steps = 0;
+    while let Some(smaller) = S::shrink(&input)
+        .into_iter()
+        .find(|candidate| !agree::<S>(candidate))
+    {
+        input = smaller;
+        steps += 1;
+    }
+    (input, steps)
+}
+
+fn agree<S: Stress>(input: &S::Input) -> bool {
+    S::reference(input) == S::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sum of the elements, wrong for inputs containing 7.
+    struct Sum;
+
+    impl Stress for Sum {
+        type Input = Vec<u64>;
+        type Output = u64;
+        const MAX_SIZE: usize = 30;
+
+        fn generate(random: &mut Random, size: usize) -> Vec<u64> {
+            (0..size).map(|_| random.range(0..=9)).collect()
+        }
+
+        fn reference(input: &Vec<u64>) -> u64 {
+            input.iter().sum()
+        }
+
+        fn solve(input: &Vec<u64>) -> u64 {
+            input.iter().filter(|&&value| value != 7).sum()
+        }
+
+        fn shrink(input: &Vec<u64>) -> Vec<Vec<u64>> {
+            removals(input)
+        }
+    }
+
+    /// [`Sum`] without shrink candidates.
+    struct Unshrinkable;
+
+    impl Stress for Unshrinkable {
+        type Input = Vec<u64>;
+        type Output = u64;
+        const MAX_SIZE: usize = 30;
+
+        fn generate(random: &mut Random, size: usize) -> Vec<u64> {
+            Sum::generate(random, size)
+        }
+
+        fn reference(input: &Vec<u64>) -> u64 {
+            Sum::reference(input)
+        }
+
+        fn solve(input: &Vec<u64>) -> u64 {
+            Sum::solve(input)
+        }
+    }
+
+    #[test]
+    fn test_removals() {
+        assert_eq!(
+            vec![vec![2, 3], vec![1, 3], vec![1, 2]],
+            removals(&[1, 2, 3])
+        );
+        assert!(removals::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_shrink() {
+        assert_eq!((vec![7], 4), shrink::<Sum>(vec![1, 7, 2, 7, 3]));
+        assert_eq!((vec![7, 7], 0), shrink::<Unshrinkable>(vec![7, 7]));
+    }
+
+    #[test]
+    #[should_panic(expected = "input: [7]\nreference: 7\nsolver: 0")]
+    fn test_check() {
+        check::<Sum>(100, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "shrunk in 0 steps")]
+    fn test_check_unshrinkable() {
+        check::<Unshrinkable>(100, 0);
+    }
+}